@@ -0,0 +1,263 @@
+use config_cell_type_generator::char_set::CharSet;
+use das_types_std::{constants::*, packed::*, prelude::*};
+use std::collections::HashMap;
+
+/// Split an account by the characters of the given char sets, the longest character which leaves the rest of the
+/// account splittable is matched at every position, so emoji which are made of multiple code points are kept as one
+/// character, and a long character is given up when the rest can only be split with shorter ones.
+///
+/// Return the byte offset of the first code point which can not be matched if failed.
+fn split_by(char_sets: &[&CharSet], account: &str) -> Result<Vec<(String, DataType)>, usize> {
+    let mut index: HashMap<&str, DataType> = HashMap::new();
+    let mut max_length = 1;
+    for char_set in char_sets {
+        for char in char_set.chars.iter() {
+            index.entry(char.as_str()).or_insert(char_set.data_type);
+            max_length = max_length.max(char.chars().count());
+        }
+    }
+
+    let boundaries = account
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(account.len()))
+        .collect::<Vec<_>>();
+    let count = boundaries.len() - 1;
+    let char_at = |i: usize, length: usize| &account[boundaries[i]..boundaries[i + length]];
+
+    // The split of the rest of the account starting at every code point is solved from the end, only the length and
+    // DataType of the first character are kept.
+    let mut solved: Vec<Option<(usize, DataType)>> = vec![None; count];
+    for i in (0..count).rev() {
+        solved[i] = (1..=max_length.min(count - i)).rev().find_map(|length| {
+            if i + length < count && solved[i + length].is_none() {
+                return None;
+            }
+            index.get(char_at(i, length)).map(|data_type| (length, *data_type))
+        });
+    }
+
+    if solved[0].is_none() {
+        // Every code point reachable from the beginning can not be split to the end, the furthest one is reported.
+        let mut reachable = vec![false; count];
+        reachable[0] = true;
+        let mut furthest = 0;
+        for i in 0..count {
+            if !reachable[i] {
+                continue;
+            }
+            furthest = i;
+            for length in 1..=max_length.min(count - i - 1) {
+                if index.contains_key(char_at(i, length)) {
+                    reachable[i + length] = true;
+                }
+            }
+        }
+
+        return Err(boundaries[furthest]);
+    }
+
+    let mut ret = Vec::new();
+    let mut i = 0;
+    while i < count {
+        let (length, data_type) = solved[i].unwrap();
+        ret.push((char_at(i, length).to_string(), data_type));
+        i += length;
+    }
+
+    Ok(ret)
+}
+
+/// Split an account into characters tagged with the DataType of their char sets.
+///
+/// An account may only be made of the characters from the global char sets, like emoji and digit, and at most one
+/// language char set, so the language char sets are tried one by one in the order of the registry.
+pub fn split_account(char_sets: &[CharSet], account: &str) -> Result<Vec<(String, DataType)>, String> {
+    let account = account.trim_end_matches(".bit");
+    if account.is_empty() {
+        return Err(String::from("The account is empty."));
+    }
+
+    let global_sets = char_sets.iter().filter(|item| item.status == 1).collect::<Vec<_>>();
+    if let Ok(chars) = split_by(&global_sets, account) {
+        return Ok(chars);
+    }
+
+    for language_set in char_sets.iter().filter(|item| item.status != 1) {
+        let mut sets = global_sets.clone();
+        sets.push(language_set);
+        if let Ok(chars) = split_by(&sets, account) {
+            return Ok(chars);
+        }
+    }
+
+    let all_sets = char_sets.iter().collect::<Vec<_>>();
+    match split_by(&all_sets, account) {
+        Ok(_) => Err(format!(
+            "The account {}.bit mixes characters from more than one language char set.",
+            account
+        )),
+        Err(offset) => {
+            let char = account[offset..].chars().next().unwrap();
+            Err(format!(
+                "The account {}.bit contains {} 0x{} which is not in any enabled char set.",
+                account,
+                char,
+                hex::encode(char.to_string().as_bytes())
+            ))
+        }
+    }
+}
+
+/// The length of an account which is used for pricing, every character counts as 1 no matter how many bytes it has.
+pub fn account_length(chars: &[(String, DataType)]) -> usize {
+    chars.len()
+}
+
+/// The char_set_name of AccountChar is the index of CharSetType, like 0 for emoji and 2 for en.
+pub fn char_set_index(data_type: DataType) -> u32 {
+    let char_set_type = match data_type {
+        DataType::ConfigCellCharSetEmoji => CharSetType::Emoji,
+        DataType::ConfigCellCharSetDigit => CharSetType::Digit,
+        DataType::ConfigCellCharSetEn => CharSetType::En,
+        DataType::ConfigCellCharSetZhHans => CharSetType::ZhHans,
+        DataType::ConfigCellCharSetZhHant => CharSetType::ZhHant,
+        DataType::ConfigCellCharSetJa => CharSetType::Ja,
+        DataType::ConfigCellCharSetKo => CharSetType::Ko,
+        DataType::ConfigCellCharSetRu => CharSetType::Ru,
+        DataType::ConfigCellCharSetTr => CharSetType::Tr,
+        DataType::ConfigCellCharSetTh => CharSetType::Th,
+        DataType::ConfigCellCharSetVi => CharSetType::Vi,
+        _ => panic!("{:?} is not the DataType of a char set.", data_type),
+    };
+
+    char_set_type as u32
+}
+
+pub fn gen_account_chars(chars: &[(String, DataType)]) -> AccountChars {
+    let mut builder = AccountChars::new_builder();
+    for (char, data_type) in chars {
+        builder = builder.push(
+            AccountChar::new_builder()
+                .char_set_name(Uint32::from(char_set_index(*data_type)))
+                .bytes(Bytes::from(char.as_bytes().to_vec()))
+                .build(),
+        );
+    }
+
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_account_into_char_set_indexes() {
+        let char_sets = vec![
+            CharSet {
+                name: String::from("digit"),
+                data_type: DataType::ConfigCellCharSetDigit,
+                file: String::from("char_set_digit.txt"),
+                status: 1,
+                chars: vec![String::from("0"), String::from("1")],
+                warnings: Vec::new(),
+            },
+            CharSet {
+                name: String::from("en"),
+                data_type: DataType::ConfigCellCharSetEn,
+                file: String::from("char_set_en.txt"),
+                status: 0,
+                chars: vec![String::from("a"), String::from("b")],
+                warnings: Vec::new(),
+            },
+        ];
+        let chars = split_account(&char_sets, "a1.bit").unwrap();
+        let indexes = chars
+            .iter()
+            .map(|(char, data_type)| (char.as_str(), char_set_index(*data_type)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(indexes, vec![("a", 2), ("1", 1)]);
+    }
+
+    #[test]
+    fn test_split_account_keeps_emoji_sequences() {
+        let char_sets = vec![
+            CharSet {
+                name: String::from("emoji"),
+                data_type: DataType::ConfigCellCharSetEmoji,
+                file: String::from("char_set_emoji.txt"),
+                status: 1,
+                chars: vec![String::from("👍"), String::from("👍🏻")],
+                warnings: Vec::new(),
+            },
+            CharSet {
+                name: String::from("digit"),
+                data_type: DataType::ConfigCellCharSetDigit,
+                file: String::from("char_set_digit.txt"),
+                status: 1,
+                chars: vec![String::from("0")],
+                warnings: Vec::new(),
+            },
+        ];
+        let chars = split_account(&char_sets, "👍🏻0👍").unwrap();
+
+        assert_eq!(
+            chars,
+            vec![
+                (String::from("👍🏻"), DataType::ConfigCellCharSetEmoji),
+                (String::from("0"), DataType::ConfigCellCharSetDigit),
+                (String::from("👍"), DataType::ConfigCellCharSetEmoji),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_account_gives_up_the_longest_match() {
+        // The longest match ab leaves c which is not a character, so the account is split into a and bc.
+        let char_sets = vec![CharSet {
+            name: String::from("en"),
+            data_type: DataType::ConfigCellCharSetEn,
+            file: String::from("char_set_en.txt"),
+            status: 0,
+            chars: vec![String::from("a"), String::from("ab"), String::from("bc")],
+            warnings: Vec::new(),
+        }];
+        let chars = split_account(&char_sets, "abc").unwrap();
+
+        assert_eq!(
+            chars,
+            vec![
+                (String::from("a"), DataType::ConfigCellCharSetEn),
+                (String::from("bc"), DataType::ConfigCellCharSetEn),
+            ]
+        );
+        assert_eq!(split_by(&[&char_sets[0]], "abcd"), Err(3));
+    }
+
+    #[test]
+    fn test_split_account_rejects_mixed_languages() {
+        let char_sets = vec![
+            CharSet {
+                name: String::from("en"),
+                data_type: DataType::ConfigCellCharSetEn,
+                file: String::from("char_set_en.txt"),
+                status: 0,
+                chars: vec![String::from("a")],
+                warnings: Vec::new(),
+            },
+            CharSet {
+                name: String::from("ja"),
+                data_type: DataType::ConfigCellCharSetJa,
+                file: String::from("char_set_ja.txt"),
+                status: 0,
+                chars: vec![String::from("あ")],
+                warnings: Vec::new(),
+            },
+        ];
+
+        assert!(split_account(&char_sets, "aあ").is_err());
+        assert!(split_account(&char_sets, "c").is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use config_cell_type_generator::char_set::CharSet;
+use das_types_std::constants::*;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::Path,
+};
+
+use super::account_chars::split_account;
+
+/// Load accounts from a file, every line is an account with or without the .bit suffix, lines start with # are
+/// comments.
+pub fn load_accounts(path: &Path) -> Vec<String> {
+    let data = fs::read_to_string(path).expect(format!("Expect file {} exist.", path.display()).as_str());
+    data.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.trim_end_matches(".bit").to_string())
+        .collect()
+}
+
+pub struct BrokenChar {
+    pub data_type: DataType,
+    pub char: String,
+    pub accounts: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct CharSetImpact {
+    /// Accounts which become invalid grouped by the characters removed from their char sets.
+    pub broken_chars: Vec<BrokenChar>,
+    /// Accounts which are already invalid with the previous char sets.
+    pub invalid_before: Vec<(String, String)>,
+}
+
+impl CharSetImpact {
+    pub fn broken_account_count(&self) -> usize {
+        self.broken_chars
+            .iter()
+            .flat_map(|item| item.accounts.iter())
+            .collect::<HashSet<_>>()
+            .len()
+    }
+}
+
+/// Find the accounts which are valid with the previous char sets but become invalid with the current char sets.
+///
+/// The AccountChars on chain are not loaded, instead every account is split with the previous char sets the same way
+/// as it was registered, and it becomes invalid once any of its characters is removed from the char set it is split
+/// into, even if the account could be split with other char sets.
+pub fn find_broken_accounts(previous: &[CharSet], current: &[CharSet], accounts: &[String]) -> CharSetImpact {
+    let current_chars = current
+        .iter()
+        .map(|char_set| (char_set.data_type as u32, char_set.chars.iter().collect::<HashSet<_>>()))
+        .collect::<HashMap<_, _>>();
+
+    let mut impact = CharSetImpact::default();
+    let mut broken_chars: BTreeMap<(u32, String), BrokenChar> = BTreeMap::new();
+    for account in accounts {
+        let chars = match split_account(previous, account) {
+            Ok(chars) => chars,
+            Err(err) => {
+                impact.invalid_before.push((account.clone(), err));
+                continue;
+            }
+        };
+
+        let mut seen = HashSet::new();
+        let removed = chars
+            .into_iter()
+            .filter(|(char, data_type)| {
+                current_chars
+                    .get(&(*data_type as u32))
+                    .map(|chars| !chars.contains(char))
+                    .unwrap_or(true)
+            })
+            .filter(|(char, data_type)| seen.insert((*data_type as u32, char.clone())))
+            .collect::<Vec<_>>();
+        for (char, data_type) in removed {
+            broken_chars
+                .entry((data_type as u32, char.clone()))
+                .or_insert_with(|| BrokenChar {
+                    data_type,
+                    char,
+                    accounts: Vec::new(),
+                })
+                .accounts
+                .push(account.clone());
+        }
+    }
+    impact.broken_chars = broken_chars.into_values().collect();
+
+    impact
+}
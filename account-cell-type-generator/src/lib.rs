@@ -0,0 +1,3 @@
+pub mod account_chars;
+pub mod char_set_impact;
+pub mod release_plan;
@@ -1,9 +1,273 @@
+use account_cell_type_generator::{account_chars, char_set_impact, release_plan};
 use ckb_hash::blake2b_256;
+use clap::Clap;
+use config_cell_type_generator::{capacity, char_set, network::Network, price, release, util};
 use das_types_std::{constants::*, packed::*, prelude::*, util as das_util};
 use faster_hex::hex_string;
-use std::convert::TryFrom;
+use std::{convert::TryFrom, path::PathBuf};
+
+#[derive(Clap, Debug)]
+#[clap(version = "0.1.0", author = "Link Xie. <xieaolin@gmail.com>")]
+struct Options {
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Clap, Debug)]
+enum Command {
+    #[clap(about = "Split an account into characters of the enabled char sets and build its AccountChars.")]
+    AccountChars(AccountCharsOptions),
+    #[clap(about = "List the registered accounts which become invalid with the current char sets.")]
+    CharSetImpact(CharSetImpactOptions),
+    #[clap(about = "Calculate the register and renew cost of an account in USD and CKB.")]
+    Price(PriceOptions),
+    #[clap(about = "Calculate the lucky number of a release rate and simulate which accounts it releases.")]
+    Release(ReleaseOptions),
+}
+
+#[derive(Clap, Debug)]
+struct AccountCharsOptions {
+    #[clap(long = "account", about = "The account to split, like das.bit .")]
+    account: String,
+}
+
+#[derive(Clap, Debug)]
+struct CharSetImpactOptions {
+    #[clap(long = "accounts", about = "The file of registered accounts, one account per line.")]
+    accounts: String,
+    #[clap(
+        long = "previous-data",
+        about = "The data directory of the char sets on chain, the ones without char_set_registry.toml work too."
+    )]
+    previous_data: String,
+    #[clap(long = "data", about = "The data directory of the new char sets, ./data by default.")]
+    data: Option<String>,
+}
+
+#[derive(Clap, Debug)]
+struct PriceOptions {
+    #[clap(long = "account", about = "The account whose length is counted by characters.")]
+    account: Option<String>,
+    #[clap(long = "length", about = "The length of account when --account is not given.")]
+    length: Option<usize>,
+    #[clap(long = "years", default_value = "1", about = "The years to register or renew.")]
+    years: u64,
+    #[clap(long = "invited", about = "Apply the discount for invited accounts.")]
+    invited: bool,
+    #[clap(long = "quote", about = "CKB price in USD with 6 decimals, like 3500 for 0.0035.")]
+    quote: u64,
+    #[clap(long = "network", about = "The network of prices, mainnet, testnet or dev.")]
+    network: Option<String>,
+}
+
+#[derive(Clap, Debug)]
+struct ReleaseOptions {
+    #[clap(
+        long = "rate",
+        about = "The release rate like 6000 for 60%, the current rate by default."
+    )]
+    rate: Option<u32>,
+    #[clap(long = "accounts", about = "The file of candidate accounts, one account per line.")]
+    accounts: Option<String>,
+    #[clap(long = "list", about = "List the released accounts of every length.")]
+    list: bool,
+    #[clap(
+        long = "network",
+        about = "The network of char sets and rates, mainnet, testnet or dev."
+    )]
+    network: Option<String>,
+}
 
 fn main() {
+    let options: Options = Options::parse();
+
+    match options.command {
+        Some(Command::AccountChars(options)) => gen_account_chars(options),
+        Some(Command::CharSetImpact(options)) => check_char_set_impact(options),
+        Some(Command::Price(options)) => calc_price(options),
+        Some(Command::Release(options)) => plan_release(options),
+        None => gen_init_account_chain(),
+    }
+}
+
+fn gen_account_chars(options: AccountCharsOptions) {
+    let char_sets = char_set::load_char_sets(&util::data_dir(), Network::current());
+    let chars = match account_chars::split_account(&char_sets, &options.account) {
+        Ok(chars) => chars,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    };
+
+    for (char, data_type) in chars.iter() {
+        println!("{} 0x{} {:?}", char, hex::encode(char.as_bytes()), data_type);
+    }
+    println!("Length: {}", account_chars::account_length(&chars));
+
+    let entity = account_chars::gen_account_chars(&chars);
+    println!("AccountChars: 0x{}", hex_string(entity.as_slice()).unwrap());
+}
+
+fn check_char_set_impact(options: CharSetImpactOptions) {
+    let data_dir = options.data.map(PathBuf::from).unwrap_or_else(util::data_dir);
+    let previous = char_set::load_previous_char_sets(&PathBuf::from(options.previous_data), Network::current());
+    let current = char_set::load_char_sets(&data_dir, Network::current());
+    let accounts = char_set_impact::load_accounts(&PathBuf::from(options.accounts));
+
+    let impact = char_set_impact::find_broken_accounts(&previous, &current, &accounts);
+    println!(
+        "Accounts which become invalid: {} of {}",
+        impact.broken_account_count(),
+        accounts.len()
+    );
+    for item in impact.broken_chars.iter() {
+        println!(
+            "  {:?} {} 0x{}: {} accounts",
+            item.data_type,
+            item.char,
+            hex::encode(item.char.as_bytes()),
+            item.accounts.len()
+        );
+        for account in item.accounts.iter() {
+            println!("    {}.bit", account);
+        }
+    }
+
+    if !impact.invalid_before.is_empty() {
+        println!();
+        println!(
+            "Accounts which are already invalid with the previous char sets: {}",
+            impact.invalid_before.len()
+        );
+        for (_, err) in impact.invalid_before.iter() {
+            println!("  {}", err);
+        }
+    }
+
+    if impact.broken_account_count() > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn calc_price(options: PriceOptions) {
+    let network = match options.network {
+        Some(network) => network.parse::<Network>().unwrap_or_else(|err| panic!("{}", err)),
+        None => Network::current(),
+    };
+    if options.quote == 0 {
+        panic!("The quote should be more than 0.");
+    }
+
+    // The bytes of the account with the .bit suffix are stored in AccountCell, every character is counted as 1 byte
+    // when only --length is given.
+    let (length, account_size) = match (options.account, options.length) {
+        (Some(account), _) => {
+            let char_sets = char_set::load_char_sets(&util::data_dir(), network);
+            match account_chars::split_account(&char_sets, &account) {
+                Ok(chars) => (
+                    account_chars::account_length(&chars),
+                    account.trim_end_matches(".bit").len() + ".bit".len(),
+                ),
+                Err(err) => {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                }
+            }
+        }
+        (None, Some(length)) => (length, length + ".bit".len()),
+        (None, None) => panic!("Either --account or --length is required."),
+    };
+
+    let item = price::price_of_length(price::prices_of(network), length);
+    let discount = if options.invited { price::INVITED_DISCOUNT } else { 0 };
+    let register_usd = price::discount_price(item.new, discount) * options.years;
+    let register_ckb = price::calc_yearly_capacity(item.new, options.quote, discount) * options.years;
+    let storage_ckb = capacity::account_cell_capacity(account_size);
+    let renew_usd = item.renew * options.years;
+    let renew_ckb = price::calc_yearly_capacity(item.renew, options.quote, 0) * options.years;
+
+    println!("Network: {}", network);
+    println!("Length: {}, the price of length {} is used", length, item.length);
+    println!(
+        "Register {} years: {} USD, {} shannons{}",
+        options.years,
+        price::format_usd(register_usd),
+        register_ckb,
+        if options.invited {
+            format!(", {}% discount for invited", discount as u64 * 100 / price::RATE_BASE)
+        } else {
+            String::new()
+        }
+    );
+    println!(
+        "AccountCell of {} bytes: {} shannons, register in total: {} shannons",
+        account_size,
+        storage_ckb,
+        register_ckb + storage_ckb
+    );
+    println!(
+        "Renew {} years: {} USD, {} shannons",
+        options.years,
+        price::format_usd(renew_usd),
+        renew_ckb
+    );
+}
+
+fn plan_release(options: ReleaseOptions) {
+    let network = match options.network {
+        Some(network) => network.parse::<Network>().unwrap_or_else(|err| panic!("{}", err)),
+        None => Network::current(),
+    };
+    let rate = options.rate.unwrap_or_else(|| release::release_rate_of(network));
+    let lucky_number = release::lucky_number_of(rate);
+    println!(
+        "Release rate: {}/{}, lucky_number: {}",
+        rate,
+        price::RATE_BASE,
+        lucky_number
+    );
+
+    let accounts = match options.accounts {
+        Some(accounts) => char_set_impact::load_accounts(&PathBuf::from(accounts)),
+        None => return,
+    };
+    let char_sets = char_set::load_char_sets(&util::data_dir(), network);
+    let plan = release_plan::plan_release(&char_sets, &accounts, lucky_number);
+
+    println!(
+        "Accounts released: {} of {}, accounts of {} or more characters are always released",
+        plan.released_count(),
+        accounts.len(),
+        release::RELEASE_FREE_LENGTH
+    );
+    for (length, item) in plan.by_length.iter() {
+        println!(
+            "  length {:>2}: {:>8} of {:>8} released",
+            length,
+            item.released.len(),
+            item.total
+        );
+        if options.list {
+            for account in item.released.iter() {
+                println!("    {}.bit", account);
+            }
+        }
+    }
+
+    if !plan.invalid.is_empty() {
+        println!();
+        println!(
+            "Accounts which can not be registered with the char sets: {}",
+            plan.invalid.len()
+        );
+        for (_, err) in plan.invalid.iter() {
+            println!("  {}", err);
+        }
+    }
+}
+
+fn gen_init_account_chain() {
     let id: Vec<u8> = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
     let next: Vec<u8> = vec![
         255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
@@ -0,0 +1,47 @@
+use config_cell_type_generator::{char_set::CharSet, release};
+use std::collections::BTreeMap;
+
+use super::account_chars::{account_length, split_account};
+
+#[derive(Default)]
+pub struct LengthRelease {
+    pub total: usize,
+    pub released: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct ReleasePlan {
+    /// The accounts grouped by their lengths in characters.
+    pub by_length: BTreeMap<usize, LengthRelease>,
+    /// Accounts which can not be split with the char sets, they can not be registered at all.
+    pub invalid: Vec<(String, String)>,
+}
+
+impl ReleasePlan {
+    pub fn released_count(&self) -> usize {
+        self.by_length.values().map(|item| item.released.len()).sum()
+    }
+}
+
+/// Simulate which accounts can be registered with the lucky number, in the same way as the contracts.
+pub fn plan_release(char_sets: &[CharSet], accounts: &[String], lucky_number: u32) -> ReleasePlan {
+    let mut plan = ReleasePlan::default();
+    for account in accounts {
+        let chars = match split_account(char_sets, account) {
+            Ok(chars) => chars,
+            Err(err) => {
+                plan.invalid.push((account.clone(), err));
+                continue;
+            }
+        };
+
+        let length = account_length(&chars);
+        let item = plan.by_length.entry(length).or_default();
+        item.total += 1;
+        if release::is_released(account, length, lucky_number) {
+            item.released.push(account.clone());
+        }
+    }
+
+    plan
+}
@@ -0,0 +1,332 @@
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+use super::price::RATE_BASE;
+use super::profit_rate::{distribute_bid, Party, ProfitRate, ProfitShare};
+
+/// The auction parameters in ConfigCellSecondaryMarket.
+#[derive(Debug, Clone)]
+pub struct AuctionConfig {
+    pub max_extendable_duration: u64,
+    pub duration_increment_each_bid: u64,
+    pub min_opening_price: u64,
+    pub min_increment_rate_each_bid: u32,
+}
+
+pub const AUCTION_CONFIG: AuctionConfig = AuctionConfig {
+    max_extendable_duration: 86400 * 7,
+    duration_increment_each_bid: 600,
+    min_opening_price: 200_000_000_000,
+    min_increment_rate_each_bid: 1000,
+};
+
+/// A scripted auction, the prices are in shannons and the times are unix timestamps in seconds.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuctionScript {
+    pub opening_price: u64,
+    pub started_at: u64,
+    /// The duration before any bid extends it.
+    pub duration: u64,
+    pub bids: Vec<Bid>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Bid {
+    pub bidder: String,
+    pub price: u64,
+    pub timestamp: u64,
+    #[serde(default = "default_true")]
+    pub inviter: bool,
+    #[serde(default = "default_true")]
+    pub channel: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl AuctionScript {
+    pub fn load(path: &Path) -> Self {
+        let data = fs::read_to_string(path).expect(format!("Expect file {} exist.", path.display()).as_str());
+        toml::from_str(&data).expect(format!("Expect file {} to be valid.", path.display()).as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BidResult {
+    pub bid: Bid,
+    /// The reason why the bid is rejected, the other fields are empty if it is some.
+    pub error: Option<String>,
+    pub ends_at: u64,
+    /// The previous bidder and the price refunded to them.
+    pub refund: Option<(String, u64)>,
+    /// The split of the increment of the bid, or the whole price for the first bid.
+    pub shares: Vec<ProfitShare>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuctionResult {
+    pub ends_at: u64,
+    pub bids: Vec<BidResult>,
+    /// The winner and the final price.
+    pub winner: Option<(String, u64)>,
+}
+
+impl AuctionResult {
+    /// The total amount a party receives from all the valid bids, the refunds are not included.
+    pub fn total_of(&self, party: Party) -> u64 {
+        self.bids
+            .iter()
+            .flat_map(|item| item.shares.iter())
+            .filter(|share| share.party == party)
+            .map(|share| share.amount)
+            .sum()
+    }
+}
+
+/// Check the opening price and the initial duration of an auction.
+pub fn check_auction_script(config: &AuctionConfig, script: &AuctionScript) -> Vec<String> {
+    let mut errors = Vec::new();
+    if script.opening_price < config.min_opening_price {
+        errors.push(format!(
+            "The opening price {} is less than auction_min_opening_price {}.",
+            script.opening_price, config.min_opening_price
+        ));
+    }
+    if script.duration == 0 {
+        errors.push(String::from("The duration should be more than 0."));
+    }
+
+    errors
+}
+
+/// Replay the bids of an auction in order.
+///
+/// - The first bid should be at least the opening price, every following bid should be at least
+///   auction_min_increment_rate_each_bid more than the highest bid.
+/// - A bid should be made before the auction ends, and every valid bid extends the end by
+///   auction_duration_increment_each_bid, but no more than auction_max_extendable_duration in total.
+/// - The previous bidder is refunded and the bid is split by distribute_bid.
+pub fn simulate_auction(config: &AuctionConfig, rate: &ProfitRate, script: &AuctionScript) -> AuctionResult {
+    let mut ends_at = script.started_at.saturating_add(script.duration);
+    let max_ends_at = ends_at.saturating_add(config.max_extendable_duration);
+    let mut highest: Option<(String, u64)> = None;
+
+    let mut bids = Vec::new();
+    for bid in script.bids.iter() {
+        // The minimum is in u128, so a huge highest bid makes the following bids invalid instead of overflowing.
+        let min_price = match &highest {
+            Some((_, price)) => {
+                *price as u128 + *price as u128 * config.min_increment_rate_each_bid as u128 / RATE_BASE as u128
+            }
+            None => script.opening_price as u128,
+        };
+
+        let error = if bid.timestamp < script.started_at {
+            Some(format!(
+                "The bid is made at {} before the auction starts at {}.",
+                bid.timestamp, script.started_at
+            ))
+        } else if bid.timestamp >= ends_at {
+            Some(format!(
+                "The bid is made at {} after the auction ends at {}.",
+                bid.timestamp, ends_at
+            ))
+        } else if (bid.price as u128) < min_price {
+            Some(format!(
+                "The price {} is less than the minimum {}.",
+                bid.price, min_price
+            ))
+        } else {
+            match &highest {
+                Some((bidder, _)) if bidder == &bid.bidder => {
+                    Some(format!("The bidder {} is already the highest bidder.", bid.bidder))
+                }
+                _ => None,
+            }
+        };
+        if error.is_some() {
+            bids.push(BidResult {
+                bid: bid.clone(),
+                error,
+                ends_at,
+                refund: None,
+                shares: Vec::new(),
+            });
+            continue;
+        }
+
+        ends_at = ends_at
+            .saturating_add(config.duration_increment_each_bid)
+            .min(max_ends_at);
+        let refund = highest.take();
+        let shares = distribute_bid(
+            rate,
+            refund.as_ref().map(|(_, price)| *price),
+            bid.price,
+            bid.inviter,
+            bid.channel,
+        );
+        highest = Some((bid.bidder.clone(), bid.price));
+
+        bids.push(BidResult {
+            bid: bid.clone(),
+            error: None,
+            ends_at,
+            refund,
+            shares,
+        });
+    }
+
+    AuctionResult {
+        ends_at,
+        bids,
+        winner: highest,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profit_rate::PROFIT_RATE;
+
+    #[test]
+    fn test_first_bid_without_previous_bidder() {
+        let script = AuctionScript {
+            opening_price: 200_000_000_000,
+            started_at: 1_000_000,
+            duration: 3600,
+            bids: vec![Bid {
+                bidder: String::from("alice"),
+                price: 200_000_000_000,
+                timestamp: 1_000_001,
+                inviter: true,
+                channel: true,
+            }],
+        };
+        let result = simulate_auction(&AUCTION_CONFIG, &PROFIT_RATE, &script);
+
+        let first = &result.bids[0];
+        assert!(first.error.is_none());
+        assert!(first.refund.is_none());
+        assert!(first.shares.iter().all(|share| share.party != Party::PrevBidder));
+        assert_eq!(result.total_of(Party::Seller), 194_000_000_000);
+        assert_eq!(result.ends_at, 1_004_200);
+        assert_eq!(result.winner, Some((String::from("alice"), 200_000_000_000)));
+    }
+
+    #[test]
+    fn test_bid_below_min_increment_is_rejected() {
+        let script = AuctionScript {
+            opening_price: 200_000_000_000,
+            started_at: 1_000_000,
+            duration: 3600,
+            bids: vec![
+                Bid {
+                    bidder: String::from("alice"),
+                    price: 200_000_000_000,
+                    timestamp: 1_000_001,
+                    inviter: true,
+                    channel: true,
+                },
+                // The minimum is 10% more than the highest bid, that is 2200 CKB.
+                Bid {
+                    bidder: String::from("bob"),
+                    price: 219_999_999_999,
+                    timestamp: 1_000_002,
+                    inviter: true,
+                    channel: true,
+                },
+                Bid {
+                    bidder: String::from("carol"),
+                    price: 220_000_000_000,
+                    timestamp: 1_000_003,
+                    inviter: true,
+                    channel: true,
+                },
+            ],
+        };
+        let result = simulate_auction(&AUCTION_CONFIG, &PROFIT_RATE, &script);
+
+        assert!(result.bids[1].error.is_some());
+        assert!(result.bids[1].shares.is_empty());
+        assert!(result.bids[2].error.is_none());
+        assert_eq!(result.bids[2].refund, Some((String::from("alice"), 200_000_000_000)));
+        assert_eq!(result.total_of(Party::PrevBidder), 9_400_000_000);
+        assert_eq!(result.winner, Some((String::from("carol"), 220_000_000_000)));
+    }
+
+    #[test]
+    fn test_extension_is_capped_at_max_extendable_duration() {
+        let config = AuctionConfig {
+            max_extendable_duration: 1000,
+            ..AUCTION_CONFIG
+        };
+        let script = AuctionScript {
+            opening_price: 200_000_000_000,
+            started_at: 1_000_000,
+            duration: 3600,
+            bids: vec![
+                Bid {
+                    bidder: String::from("alice"),
+                    price: 200_000_000_000,
+                    timestamp: 1_000_001,
+                    inviter: true,
+                    channel: true,
+                },
+                Bid {
+                    bidder: String::from("bob"),
+                    price: 220_000_000_000,
+                    timestamp: 1_000_002,
+                    inviter: true,
+                    channel: true,
+                },
+                Bid {
+                    bidder: String::from("alice"),
+                    price: 242_000_000_000,
+                    timestamp: 1_000_003,
+                    inviter: true,
+                    channel: true,
+                },
+            ],
+        };
+        let result = simulate_auction(&config, &PROFIT_RATE, &script);
+
+        let ends_at = result.bids.iter().map(|item| item.ends_at).collect::<Vec<_>>();
+        assert_eq!(ends_at, vec![1_004_200, 1_004_600, 1_004_600]);
+        assert_eq!(result.ends_at, 1_004_600);
+    }
+
+    #[test]
+    fn test_huge_bid_does_not_overflow() {
+        let script = AuctionScript {
+            opening_price: 200_000_000_000,
+            started_at: u64::MAX - 10,
+            duration: 3600,
+            bids: vec![
+                Bid {
+                    bidder: String::from("alice"),
+                    price: u64::MAX,
+                    timestamp: u64::MAX - 9,
+                    inviter: false,
+                    channel: false,
+                },
+                Bid {
+                    bidder: String::from("bob"),
+                    price: u64::MAX,
+                    timestamp: u64::MAX - 8,
+                    inviter: false,
+                    channel: false,
+                },
+            ],
+        };
+        let result = simulate_auction(&AUCTION_CONFIG, &PROFIT_RATE, &script);
+
+        assert!(result.bids[0].error.is_none());
+        assert!(result.bids[1].error.is_some());
+        assert_eq!(result.ends_at, u64::MAX);
+        assert_eq!(result.winner, Some((String::from("alice"), u64::MAX)));
+    }
+}
@@ -0,0 +1,137 @@
+use super::constants::ACCOUNT_ID_LENGTH;
+use super::price::ONE_CKB;
+
+// The basic and prepared fee capacities of cells in ConfigCellAccount, ConfigCellSecondaryMarket,
+// ConfigCellReverseResolution and ConfigCellSubAccount.
+pub const ACCOUNT_CELL_BASIC_CAPACITY: u64 = 20_600_000_000;
+pub const ACCOUNT_CELL_PREPARED_FEE_CAPACITY: u64 = 100_000_000;
+pub const SALE_CELL_BASIC_CAPACITY: u64 = 20_000_000_000;
+pub const SALE_CELL_PREPARED_FEE_CAPACITY: u64 = 100_000_000;
+pub const OFFER_CELL_BASIC_CAPACITY: u64 = 20_000_000_000;
+pub const OFFER_CELL_PREPARED_FEE_CAPACITY: u64 = 100_000_000;
+pub const AUCTION_CELL_BASIC_CAPACITY: u64 = 20_000_000_000;
+pub const AUCTION_CELL_PREPARED_FEE_CAPACITY: u64 = 100_000_000;
+pub const REVERSE_RECORD_BASIC_CAPACITY: u64 = 20_000_000_000;
+pub const REVERSE_RECORD_PREPARED_FEE_CAPACITY: u64 = 100_000_000;
+pub const SUB_ACCOUNT_CELL_BASIC_CAPACITY: u64 = 20_000_000_000;
+pub const SUB_ACCOUNT_CELL_PREPARED_FEE_CAPACITY: u64 = 2_000_000_000;
+
+const CAPACITY_SIZE: usize = 8;
+// A script is 32 bytes of code_hash, 1 byte of hash_type and the args.
+const SCRIPT_BASIC_SIZE: usize = 33;
+// The args of das-lock are the owner and the manager, each is 1 byte of algorithm ID and 20 bytes of pubkey hash.
+pub const DAS_LOCK_ARGS_SIZE: usize = 42;
+// Cells with witnesses store the blake2b_256 hash of their witnesses at the beginning of cell data.
+const WITNESS_HASH_SIZE: usize = 32;
+const TIMESTAMP_SIZE: usize = 8;
+
+/// The layout of a cell in bytes, the type script is always required by DAS cells.
+#[derive(Debug, Clone)]
+pub struct CellCapacity {
+    pub name: &'static str,
+    pub lock_args_size: usize,
+    pub type_args_size: usize,
+    pub data_size: usize,
+    pub basic_capacity: u64,
+    pub prepared_fee_capacity: u64,
+}
+
+impl CellCapacity {
+    /// The occupied capacity of the cell in shannons, every byte occupies 1 CKB.
+    pub fn occupied_capacity(&self) -> u64 {
+        let size = CAPACITY_SIZE
+            + SCRIPT_BASIC_SIZE
+            + self.lock_args_size
+            + SCRIPT_BASIC_SIZE
+            + self.type_args_size
+            + self.data_size;
+        size as u64 * ONE_CKB
+    }
+
+    /// Check that the basic capacity covers the occupied capacity and the prepared fee.
+    pub fn check(&self) -> Result<(), String> {
+        let required = self.occupied_capacity() + self.prepared_fee_capacity;
+        if self.basic_capacity < required {
+            return Err(format!(
+                "The basic capacity {} CKB of {} does not cover the occupied {} CKB and the prepared fee {} CKB.",
+                format_ckb(self.basic_capacity),
+                self.name,
+                format_ckb(self.occupied_capacity()),
+                format_ckb(self.prepared_fee_capacity)
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// The layouts of cells whose basic capacities are configured, account_size is the bytes of an account with the .bit
+/// suffix stored in ReverseRecordCell.
+///
+/// The account in AccountCell is not counted, because the contracts require its capacity besides the basic capacity.
+pub fn cell_capacities(account_size: usize) -> Vec<CellCapacity> {
+    vec![
+        CellCapacity {
+            name: "AccountCell",
+            lock_args_size: DAS_LOCK_ARGS_SIZE,
+            type_args_size: 0,
+            // hash, id, next, expired_at
+            data_size: WITNESS_HASH_SIZE + ACCOUNT_ID_LENGTH * 2 + TIMESTAMP_SIZE,
+            basic_capacity: ACCOUNT_CELL_BASIC_CAPACITY,
+            prepared_fee_capacity: ACCOUNT_CELL_PREPARED_FEE_CAPACITY,
+        },
+        CellCapacity {
+            name: "AccountSaleCell",
+            lock_args_size: DAS_LOCK_ARGS_SIZE,
+            type_args_size: 0,
+            data_size: WITNESS_HASH_SIZE,
+            basic_capacity: SALE_CELL_BASIC_CAPACITY,
+            prepared_fee_capacity: SALE_CELL_PREPARED_FEE_CAPACITY,
+        },
+        CellCapacity {
+            name: "OfferCell",
+            lock_args_size: DAS_LOCK_ARGS_SIZE,
+            type_args_size: 0,
+            data_size: WITNESS_HASH_SIZE,
+            basic_capacity: OFFER_CELL_BASIC_CAPACITY,
+            prepared_fee_capacity: OFFER_CELL_PREPARED_FEE_CAPACITY,
+        },
+        CellCapacity {
+            name: "AccountAuctionCell",
+            lock_args_size: DAS_LOCK_ARGS_SIZE,
+            type_args_size: 0,
+            data_size: WITNESS_HASH_SIZE,
+            basic_capacity: AUCTION_CELL_BASIC_CAPACITY,
+            prepared_fee_capacity: AUCTION_CELL_PREPARED_FEE_CAPACITY,
+        },
+        CellCapacity {
+            name: "ReverseRecordCell",
+            lock_args_size: DAS_LOCK_ARGS_SIZE,
+            type_args_size: 0,
+            data_size: account_size,
+            basic_capacity: REVERSE_RECORD_BASIC_CAPACITY,
+            prepared_fee_capacity: REVERSE_RECORD_PREPARED_FEE_CAPACITY,
+        },
+        CellCapacity {
+            name: "SubAccountCell",
+            // The lock is always-success without args.
+            lock_args_size: 0,
+            type_args_size: ACCOUNT_ID_LENGTH,
+            // smt_root, das_profit, owner_profit
+            data_size: 32 + 8 + 8,
+            basic_capacity: SUB_ACCOUNT_CELL_BASIC_CAPACITY,
+            prepared_fee_capacity: SUB_ACCOUNT_CELL_PREPARED_FEE_CAPACITY,
+        },
+    ]
+}
+
+/// The capacity of a new AccountCell which is required besides the register fee, it is the basic capacity, the prepared
+/// fee and 1 CKB for every byte of the account with the .bit suffix.
+pub fn account_cell_capacity(account_size: usize) -> u64 {
+    ACCOUNT_CELL_BASIC_CAPACITY + ACCOUNT_CELL_PREPARED_FEE_CAPACITY + account_size as u64 * ONE_CKB
+}
+
+/// Format an amount in shannons as CKB, like 206.00000000 .
+pub fn format_ckb(amount: u64) -> String {
+    format!("{}.{:08}", amount / ONE_CKB, amount % ONE_CKB)
+}
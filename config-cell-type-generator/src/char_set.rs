@@ -0,0 +1,315 @@
+use ckb_hash::blake2b_256;
+use das_types_std::constants::*;
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::Path,
+};
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
+use super::char_set_registry::*;
+use super::constants::*;
+use super::emoji;
+use super::network::Network;
+use super::unicode_data::{UnicodeData, UNICODE_DATA_DIR};
+use super::util::{prepend_molecule_like_length, read_lines_in};
+
+pub struct CharSet {
+    pub name: String,
+    pub data_type: DataType,
+    pub file: String,
+    pub status: u8,
+    pub chars: Vec<String>,
+    /// Problems found when loading the char set, they should be fixed in the data files.
+    pub warnings: Vec<String>,
+}
+
+/// The characters in the removed file, like char_set_emoji_removed.txt, will be subtracted from the char set.
+pub fn removed_file_of(file: &str) -> String {
+    format!("{}_removed.txt", file.trim_end_matches(".txt"))
+}
+
+/// Load characters from a file, every character will be trimmed and NFC-normalized, the empty lines will be skipped.
+fn load_chars(data_dir: &Path, file: &str, warnings: &mut Vec<String>) -> Vec<String> {
+    let mut chars = Vec::new();
+    let lines = read_lines_in(data_dir, file).expect(format!("Expect file ./data/{} exist.", file).as_str());
+    for line in lines {
+        if let Ok(char) = line {
+            let cleared_char = char.trim().to_string();
+            if cleared_char.is_empty() {
+                continue;
+            }
+            if cleared_char.as_bytes().contains(&0u8) {
+                // CAREFUL! Characters which contains 0x00 are not allowed, so it exists warn the developer to review the config file.
+                panic!("File {} character {} contains 0x00 byte.", file, cleared_char);
+            }
+
+            if !is_nfc(&cleared_char) {
+                let normalized = cleared_char.nfc().collect::<String>();
+                warnings.push(format!(
+                    "{} character {} 0x{} is not NFC-normalized, it is normalized to {} 0x{}",
+                    file,
+                    cleared_char,
+                    hex::encode(cleared_char.as_bytes()),
+                    normalized,
+                    hex::encode(normalized.as_bytes())
+                ));
+                chars.push(normalized);
+            } else {
+                chars.push(cleared_char);
+            }
+        }
+    }
+
+    chars
+}
+
+pub fn load_char_set(data_dir: &Path, setting: &ResolvedCharSetSetting) -> CharSet {
+    load_char_set_with(data_dir, setting, true)
+}
+
+fn load_char_set_with(data_dir: &Path, setting: &ResolvedCharSetSetting, check_emoji: bool) -> CharSet {
+    let file = setting.file.as_str();
+    let mut warnings = Vec::new();
+    let chars = load_chars(data_dir, file, &mut warnings);
+
+    let removed_file = removed_file_of(file);
+    let removed = if data_dir.join(&removed_file).exists() {
+        load_chars(data_dir, &removed_file, &mut warnings)
+    } else {
+        Vec::new()
+    };
+
+    let removed = removed.into_iter().collect::<HashSet<_>>();
+    let mut dedup_chars = HashSet::new();
+    let mut ret = Vec::new();
+    for char in chars {
+        if removed.contains(&char) {
+            continue;
+        }
+        if dedup_chars.contains(&char) {
+            warnings.push(format!(
+                "{} find duplicated char: {} 0x{}",
+                file,
+                char,
+                hex::encode(char.as_bytes())
+            ));
+            continue;
+        }
+
+        dedup_chars.insert(char.clone());
+        ret.push(char);
+    }
+
+    if check_emoji && setting.data_type == DataType::ConfigCellCharSetEmoji {
+        let unicode = UnicodeData::load(data_dir);
+        warnings.extend(emoji::check_emoji_chars(&unicode, file, &ret));
+    }
+
+    CharSet {
+        name: setting.name.clone(),
+        data_type: setting.data_type,
+        file: file.to_string(),
+        status: setting.status,
+        chars: ret,
+        warnings,
+    }
+}
+
+/// Load the distinct characters of a char set file as they are, the removed file is not subtracted.
+pub fn load_file_chars(data_dir: &Path, file: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let mut dedup_chars = HashSet::new();
+    load_chars(data_dir, file, &mut warnings)
+        .into_iter()
+        .filter(|char| dedup_chars.insert(char.clone()))
+        .collect()
+}
+
+/// Load the char sets which are enabled for the network in the registry.
+pub fn load_char_sets(data_dir: &Path, network: Network) -> Vec<CharSet> {
+    CharSetRegistry::load(data_dir)
+        .resolve(network)
+        .iter()
+        .filter(|setting| setting.enabled)
+        .map(|setting| load_char_set(data_dir, setting))
+        .collect()
+}
+
+/// Load a char set file the same way as before char_set_registry.toml, the lines are only trimmed, so the characters
+/// are exactly the ones on chain. The removed file is not subtracted because it was not read at that time.
+fn load_legacy_char_set(data_dir: &Path, setting: &ResolvedCharSetSetting) -> CharSet {
+    let file = setting.file.as_str();
+    let lines = read_lines_in(data_dir, file).expect(format!("Expect file {} exist.", file).as_str());
+    let mut chars = Vec::new();
+    for line in lines {
+        if let Ok(char) = line {
+            let cleared_char = char.trim().to_string();
+            if cleared_char.is_empty() {
+                continue;
+            }
+            if cleared_char.as_bytes().contains(&0u8) {
+                panic!("File {} character {} contains 0x00 byte.", file, cleared_char);
+            }
+            chars.push(cleared_char);
+        }
+    }
+
+    CharSet {
+        name: setting.name.clone(),
+        data_type: setting.data_type,
+        file: file.to_string(),
+        status: setting.status,
+        chars,
+        warnings: Vec::new(),
+    }
+}
+
+/// Load the enabled char sets of a data directory which may be older than char_set_registry.toml or ./data/unicode,
+/// like a checkout of a previous release. Without the registry, the char sets which were hard-coded at that time are
+/// loaded as they were, and the emoji are not checked without the Unicode data.
+pub fn load_previous_char_sets(data_dir: &Path, network: Network) -> Vec<CharSet> {
+    if !data_dir.join(CHAR_SET_REGISTRY_FILE).is_file() {
+        return legacy_settings(data_dir)
+            .iter()
+            .map(|setting| load_legacy_char_set(data_dir, setting))
+            .collect();
+    }
+
+    let has_unicode_data = data_dir.join(UNICODE_DATA_DIR).is_dir();
+    CharSetRegistry::load(data_dir)
+        .resolve(network)
+        .iter()
+        .filter(|setting| setting.enabled)
+        .map(|setting| load_char_set_with(data_dir, setting, has_unicode_data))
+        .collect()
+}
+
+/// Load all the char sets in the registry including the disabled ones.
+pub fn load_all_char_sets(data_dir: &Path, network: Network) -> Vec<CharSet> {
+    CharSetRegistry::load(data_dir)
+        .resolve(network)
+        .iter()
+        .map(|setting| load_char_set(data_dir, setting))
+        .collect()
+}
+
+/// Find the characters which exist in more than one char set.
+pub fn find_cross_duplicates(char_sets: &[CharSet]) -> BTreeMap<String, Vec<DataType>> {
+    let mut ret: BTreeMap<String, Vec<DataType>> = BTreeMap::new();
+    for char_set in char_sets {
+        for char in char_set.chars.iter() {
+            ret.entry(char.clone()).or_default().push(char_set.data_type);
+        }
+    }
+
+    ret.into_iter().filter(|(_, data_types)| data_types.len() > 1).collect()
+}
+
+/// Join the global status and all characters with 0x00 byte as entity, the length is not prepended.
+pub fn char_set_raw(status: u8, chars: &[String]) -> Vec<u8> {
+    let mut raw: Vec<u8> = Vec::new();
+    raw.push(status); // global status
+    for key in chars {
+        raw.extend(key.as_bytes());
+        raw.extend(&[0u8]);
+    }
+
+    raw
+}
+
+/// Check if the witness of a char set will exceed the WITNESS_SIZE_LIMIT when it is put in one ConfigCell.
+pub fn exceeds_witness_limit(raw: &[u8]) -> bool {
+    WITNESS_HEADER_LENGTH + 4 + raw.len() > WITNESS_SIZE_LIMIT
+}
+
+/// Split characters into shards in their original order, every shard is as large as possible without exceeding the
+/// WITNESS_SIZE_LIMIT, so the result is deterministic for the same file.
+pub fn split_into_shards(chars: &[String]) -> Vec<Vec<String>> {
+    // The witness of a shard is the header, the length of entity, the global status and the characters.
+    let capacity = WITNESS_SIZE_LIMIT - WITNESS_HEADER_LENGTH - 4 - 1;
+
+    let mut shards = Vec::new();
+    let mut shard: Vec<String> = Vec::new();
+    let mut size = 0;
+    for char in chars {
+        let char_size = char.as_bytes().len() + 1;
+        if size + char_size > capacity {
+            shards.push(shard);
+            shard = Vec::new();
+            size = 0;
+        }
+
+        shard.push(char.clone());
+        size += char_size;
+    }
+    if !shard.is_empty() {
+        shards.push(shard);
+    }
+
+    if shards.len() > CHAR_SET_SHARD_INDEX as usize {
+        panic!("The char set needs more than {} shards.", CHAR_SET_SHARD_INDEX);
+    }
+
+    shards
+}
+
+pub fn shard_config_type(data_type: DataType, index: usize) -> u32 {
+    data_type as u32 * CHAR_SET_SHARD_CONFIG_TYPE_MULTIPLIER + index as u32
+}
+
+pub fn shard_index_config_type(data_type: DataType) -> u32 {
+    data_type as u32 * CHAR_SET_SHARD_CONFIG_TYPE_MULTIPLIER + CHAR_SET_SHARD_INDEX
+}
+
+/// Build the index of shards which is stored in its own ConfigCell, the length is not prepended.
+///
+/// The structure is: global status (1 byte) + shard count (1 byte) + for every shard: config type (4 bytes LE),
+/// character count (4 bytes LE) and the blake2b_256 hash of the shard entity (32 bytes).
+pub fn shard_index_raw(status: u8, shards: &[(u32, usize, [u8; 32])]) -> Vec<u8> {
+    if shards.len() > u8::MAX as usize {
+        panic!("The index supports 255 shards at most, but there are {}.", shards.len());
+    }
+
+    let mut raw: Vec<u8> = Vec::new();
+    raw.push(status);
+    raw.push(shards.len() as u8);
+    for (config_type, count, hash) in shards {
+        raw.extend(&config_type.to_le_bytes());
+        raw.extend(&(*count as u32).to_le_bytes());
+        raw.extend(hash);
+    }
+
+    raw
+}
+
+/// Build the entities of the ConfigCells of a char set with their config types, the length is prepended.
+///
+/// A char set which fits in one ConfigCell uses its DataType, otherwise it is split into shards followed by the index,
+/// and none of them uses the DataType, so the contracts never read the index as the char set. The config types of shards
+/// are not recognised on chain yet, so they are only built with the feature char-set-shards.
+pub fn char_set_entities(char_set: &CharSet) -> Vec<(u32, Vec<u8>)> {
+    let raw = char_set_raw(char_set.status, &char_set.chars);
+    if !exceeds_witness_limit(&raw) {
+        return vec![(char_set.data_type as u32, prepend_molecule_like_length(raw))];
+    }
+    if !cfg!(feature = "char-set-shards") {
+        panic!(
+            "The size of {:?} is more than {} bytes, this needs to modify das-contracts to support, or build with the feature char-set-shards to split it into shards which are not recognised on chain yet.",
+            char_set.data_type, WITNESS_SIZE_LIMIT
+        );
+    }
+
+    let mut entities = Vec::new();
+    let mut index = Vec::new();
+    for (i, shard) in split_into_shards(&char_set.chars).into_iter().enumerate() {
+        let config_type = shard_config_type(char_set.data_type, i);
+        let entity = prepend_molecule_like_length(char_set_raw(char_set.status, &shard));
+        index.push((config_type, shard.len(), blake2b_256(entity.as_slice())));
+        entities.push((config_type, entity));
+    }
+
+    let entity = prepend_molecule_like_length(shard_index_raw(char_set.status, &index));
+    entities.push((shard_index_config_type(char_set.data_type), entity));
+
+    entities
+}
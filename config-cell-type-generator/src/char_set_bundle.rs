@@ -0,0 +1,70 @@
+use ckb_hash::blake2b_256;
+use serde::Serialize;
+
+use super::char_set::{char_set_entities, CharSet};
+use super::network::Network;
+use super::util::wrap_config_witness;
+
+/// The version of the bundle structure, it should be increased when the structure changes.
+pub const CHAR_SET_BUNDLE_VERSION: u32 = 1;
+
+/// All char sets which are deployed as ConfigCells, it is exported for frontends and SDKs.
+#[derive(Debug, Serialize)]
+pub struct CharSetBundle {
+    pub version: u32,
+    pub generator_version: String,
+    pub network: String,
+    pub char_sets: Vec<CharSetBundleItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CharSetBundleItem {
+    pub data_type: u32,
+    pub language: String,
+    pub status: u8,
+    pub chars: Vec<String>,
+    /// The ConfigCells of the char set, more than one when the char set is split into shards.
+    pub witnesses: Vec<CharSetBundleWitness>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CharSetBundleWitness {
+    pub config_type: u32,
+    /// The blake2b_256 hash of the whole witness of the ConfigCell in hex with 0x prefix.
+    pub hash: String,
+}
+
+impl CharSetBundle {
+    pub fn new(network: Network, char_sets: &[CharSet]) -> Self {
+        let char_sets = char_sets
+            .iter()
+            .map(|char_set| {
+                let witnesses = char_set_entities(char_set)
+                    .into_iter()
+                    .map(|(config_type, entity)| CharSetBundleWitness {
+                        config_type,
+                        hash: format!(
+                            "0x{}",
+                            hex::encode(blake2b_256(wrap_config_witness(config_type, &entity)))
+                        ),
+                    })
+                    .collect();
+
+                CharSetBundleItem {
+                    data_type: char_set.data_type as u32,
+                    language: char_set.name.clone(),
+                    status: char_set.status,
+                    chars: char_set.chars.clone(),
+                    witnesses,
+                }
+            })
+            .collect();
+
+        CharSetBundle {
+            version: CHAR_SET_BUNDLE_VERSION,
+            generator_version: env!("CARGO_PKG_VERSION").to_string(),
+            network: network.to_string(),
+            char_sets,
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use serde::Deserialize;
+use std::{collections::HashSet, fs, ops::RangeInclusive, path::Path};
+use unicode_normalization::UnicodeNormalization;
+
+use super::unicode_data::*;
+use super::util::read_lines_in;
+
+/// The recipe of a char set file, like char_set_ja.recipe.toml for char_set_ja.txt.
+///
+/// Steps are applied in order, an included character keeps the position where it is included first, an excluded
+/// character is removed from the characters included by the previous steps.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CharSetRecipe {
+    pub steps: Vec<RecipeStep>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepAction {
+    #[default]
+    Include,
+    Exclude,
+}
+
+/// A step selects characters in one of the following ways:
+///
+/// - code points in order which match all of `range`, `block`, `script`, `property` and `max_age` which are given;
+/// - lines of a list file in ./data, like unicode/lists/joyo_kanji.txt, optionally only the first `limit` lines;
+/// - the `chars` themselves.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RecipeStep {
+    #[serde(default)]
+    pub action: StepAction,
+    pub range: Option<String>,
+    pub block: Option<String>,
+    pub script: Option<String>,
+    pub property: Option<String>,
+    pub max_age: Option<String>,
+    pub list: Option<String>,
+    pub limit: Option<usize>,
+    pub chars: Option<Vec<String>>,
+}
+
+pub fn recipe_file_of(file: &str) -> String {
+    format!("{}.recipe.toml", file.trim_end_matches(".txt"))
+}
+
+fn contains(ranges: &[RangeInclusive<u32>], code_point: u32) -> bool {
+    ranges.iter().any(|range| range.contains(&code_point))
+}
+
+impl RecipeStep {
+    fn selects_code_points(&self) -> bool {
+        self.range.is_some() || self.block.is_some() || self.script.is_some() || self.property.is_some()
+    }
+
+    fn validate(&self, data_dir: &Path, unicode: &UnicodeData) -> Vec<String> {
+        let mut errors = Vec::new();
+        let sources = [self.selects_code_points(), self.list.is_some(), self.chars.is_some()];
+        if sources.iter().filter(|source| **source).count() != 1 {
+            errors.push(String::from(
+                "A step should select characters by code points, a list or chars, and only one of them.",
+            ));
+        }
+
+        if let Some(range) = &self.range {
+            if let Err(err) = parse_code_point_range(range) {
+                errors.push(err);
+            }
+        }
+        if let Some(block) = &self.block {
+            if unicode.block(block).is_none() {
+                errors.push(format!("The block {} is unknown.", block));
+            }
+        }
+        if let Some(script) = &self.script {
+            if unicode.script(script).is_none() {
+                errors.push(format!("The script {} is unknown.", script));
+            }
+        }
+        if let Some(property) = &self.property {
+            if unicode.property(property).is_none() {
+                errors.push(format!("The property {} is unknown.", property));
+            }
+        }
+        if let Some(max_age) = &self.max_age {
+            if !self.selects_code_points() {
+                errors.push(String::from("The max_age only works with code points."));
+            }
+            if let Err(err) = parse_age(max_age) {
+                errors.push(err);
+            }
+        }
+        if let Some(list) = &self.list {
+            if !data_dir.join(list).is_file() {
+                errors.push(format!("The list {} does not exist.", list));
+            }
+        }
+        if self.limit.is_some() && self.list.is_none() {
+            errors.push(String::from("The limit only works with a list."));
+        }
+
+        errors
+    }
+
+    fn select_code_points(&self, unicode: &UnicodeData) -> Vec<String> {
+        let range = self
+            .range
+            .as_ref()
+            .map(|range| vec![parse_code_point_range(range).unwrap()]);
+        let block = self.block.as_ref().map(|block| unicode.block(block).unwrap().to_vec());
+        let script = self
+            .script
+            .as_ref()
+            .map(|script| unicode.script(script).unwrap().to_vec());
+        let property = self
+            .property
+            .as_ref()
+            .map(|property| unicode.property(property).unwrap().to_vec());
+        let max_age = self.max_age.as_ref().map(|max_age| parse_age(max_age).unwrap());
+
+        // Walk through the narrowest selector and filter the code points by the others.
+        let mut candidates = range
+            .clone()
+            .or_else(|| block.clone())
+            .or_else(|| property.clone())
+            .or_else(|| script.clone())
+            .unwrap();
+        candidates.sort_by_key(|range| *range.start());
+
+        let mut ret = Vec::new();
+        for code_point in candidates.into_iter().flatten() {
+            let matched = [&range, &block, &script, &property].iter().all(|ranges| match ranges {
+                Some(ranges) => contains(ranges, code_point),
+                None => true,
+            });
+            if !matched {
+                continue;
+            }
+
+            // The unassigned code points are always skipped.
+            match (unicode.age(code_point), max_age) {
+                (None, _) => continue,
+                (Some(age), Some(max_age)) if age > max_age => continue,
+                _ => {}
+            }
+
+            if let Some(char) = std::char::from_u32(code_point) {
+                ret.push(char.to_string());
+            }
+        }
+
+        ret
+    }
+
+    fn select_list(&self, data_dir: &Path) -> Vec<String> {
+        let list = self.list.as_ref().unwrap();
+        let lines = read_lines_in(data_dir, list).expect(format!("Expect file ./data/{} exist.", list).as_str());
+
+        let mut ret = Vec::new();
+        for line in lines {
+            if let Ok(line) = line {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                ret.push(line.to_string());
+            }
+        }
+        if let Some(limit) = self.limit {
+            ret.truncate(limit);
+        }
+
+        ret
+    }
+
+    fn select(&self, data_dir: &Path, unicode: &UnicodeData) -> Vec<String> {
+        let chars = if self.selects_code_points() {
+            self.select_code_points(unicode)
+        } else if self.list.is_some() {
+            self.select_list(data_dir)
+        } else {
+            self.chars.clone().unwrap()
+        };
+
+        chars.iter().map(|char| char.nfc().collect()).collect()
+    }
+}
+
+impl CharSetRecipe {
+    /// Load the recipe of a char set file, None means the char set file is maintained by hand.
+    pub fn load(data_dir: &Path, file: &str, unicode: &UnicodeData) -> Option<Self> {
+        let recipe_file = recipe_file_of(file);
+        let path = data_dir.join(&recipe_file);
+        if !path.exists() {
+            return None;
+        }
+
+        let data = fs::read_to_string(&path).expect(format!("Expect file ./data/{} exist.", recipe_file).as_str());
+        let recipe: CharSetRecipe =
+            toml::from_str(&data).expect(format!("Expect file ./data/{} to be valid.", recipe_file).as_str());
+
+        let errors = recipe
+            .steps
+            .iter()
+            .enumerate()
+            .flat_map(|(i, step)| {
+                step.validate(data_dir, unicode)
+                    .into_iter()
+                    .map(move |err| format!("Step {}: {}", i + 1, err))
+            })
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            panic!("File {} is invalid:\n{}", recipe_file, errors.join("\n"));
+        }
+
+        Some(recipe)
+    }
+
+    pub fn build(&self, data_dir: &Path, unicode: &UnicodeData) -> Vec<String> {
+        let mut chars: Vec<String> = Vec::new();
+        let mut included = HashSet::new();
+        for step in self.steps.iter() {
+            let selected = step.select(data_dir, unicode);
+            match step.action {
+                StepAction::Include => {
+                    for char in selected {
+                        if included.insert(char.clone()) {
+                            chars.push(char);
+                        }
+                    }
+                }
+                StepAction::Exclude => {
+                    let selected = selected.into_iter().collect::<HashSet<_>>();
+                    chars.retain(|char| !selected.contains(char));
+                    included.retain(|char| !selected.contains(char));
+                }
+            }
+        }
+
+        chars
+    }
+}
@@ -0,0 +1,173 @@
+use das_types_std::constants::*;
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+};
+
+use super::network::Network;
+
+pub const CHAR_SET_REGISTRY_FILE: &str = "char_set_registry.toml";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CharSetRegistry {
+    pub char_sets: Vec<CharSetSetting>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CharSetSetting {
+    pub name: String,
+    pub data_type: String,
+    pub file: String,
+    pub status: u8,
+    pub enabled: bool,
+    #[serde(default)]
+    pub networks: HashMap<String, NetworkOverride>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetworkOverride {
+    pub status: Option<u8>,
+    pub enabled: Option<bool>,
+}
+
+/// The setting of a char set after the overrides of a network are applied.
+#[derive(Debug, Clone)]
+pub struct ResolvedCharSetSetting {
+    pub name: String,
+    pub data_type: DataType,
+    pub file: String,
+    pub status: u8,
+    pub enabled: bool,
+}
+
+fn parse_data_type(name: &str) -> Option<DataType> {
+    let data_type = match name {
+        "ConfigCellCharSetEmoji" => DataType::ConfigCellCharSetEmoji,
+        "ConfigCellCharSetDigit" => DataType::ConfigCellCharSetDigit,
+        "ConfigCellCharSetEn" => DataType::ConfigCellCharSetEn,
+        "ConfigCellCharSetZhHans" => DataType::ConfigCellCharSetZhHans,
+        "ConfigCellCharSetZhHant" => DataType::ConfigCellCharSetZhHant,
+        "ConfigCellCharSetJa" => DataType::ConfigCellCharSetJa,
+        "ConfigCellCharSetKo" => DataType::ConfigCellCharSetKo,
+        "ConfigCellCharSetRu" => DataType::ConfigCellCharSetRu,
+        "ConfigCellCharSetTr" => DataType::ConfigCellCharSetTr,
+        "ConfigCellCharSetTh" => DataType::ConfigCellCharSetTh,
+        "ConfigCellCharSetVi" => DataType::ConfigCellCharSetVi,
+        _ => return None,
+    };
+
+    Some(data_type)
+}
+
+/// The char sets which were enabled before char_set_registry.toml with the names in the registry, they are the same on
+/// all networks.
+const LEGACY_CHAR_SETS: [(&str, DataType, &str, u8); 8] = [
+    ("emoji", DataType::ConfigCellCharSetEmoji, "char_set_emoji.txt", 1),
+    (
+        "digit",
+        DataType::ConfigCellCharSetDigit,
+        "char_set_digit_and_symbol.txt",
+        1,
+    ),
+    ("en", DataType::ConfigCellCharSetEn, "char_set_en.txt", 0),
+    ("ja", DataType::ConfigCellCharSetJa, "char_set_ja.txt", 0),
+    ("ko", DataType::ConfigCellCharSetKo, "char_set_ko.txt", 0),
+    ("tr", DataType::ConfigCellCharSetTr, "char_set_tr.txt", 0),
+    ("th", DataType::ConfigCellCharSetTh, "char_set_th.txt", 0),
+    ("vi", DataType::ConfigCellCharSetVi, "char_set_vi.txt", 0),
+];
+
+/// The settings of a data directory from before char_set_registry.toml, the char sets whose files do not exist are
+/// skipped because they were added later.
+pub fn legacy_settings(data_dir: &Path) -> Vec<ResolvedCharSetSetting> {
+    LEGACY_CHAR_SETS
+        .iter()
+        .filter(|(_, _, file, _)| data_dir.join(file).is_file())
+        .map(|(name, data_type, file, status)| ResolvedCharSetSetting {
+            name: name.to_string(),
+            data_type: *data_type,
+            file: file.to_string(),
+            status: *status,
+            enabled: true,
+        })
+        .collect()
+}
+
+impl CharSetRegistry {
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(CHAR_SET_REGISTRY_FILE);
+        let data = fs::read_to_string(&path)
+            .expect(format!("Expect file ./data/{} exist.", CHAR_SET_REGISTRY_FILE).as_str());
+        let registry: CharSetRegistry = toml::from_str(&data)
+            .expect(format!("Expect file ./data/{} to be valid.", CHAR_SET_REGISTRY_FILE).as_str());
+
+        let errors = registry.validate(data_dir);
+        if !errors.is_empty() {
+            panic!("File {} is invalid:\n{}", CHAR_SET_REGISTRY_FILE, errors.join("\n"));
+        }
+
+        registry
+    }
+
+    /// Check that every DataType is known and unique, every name is unique and every referenced file exists.
+    pub fn validate(&self, data_dir: &Path) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut names = HashSet::new();
+        let mut data_types = HashSet::new();
+        for setting in self.char_sets.iter() {
+            if !names.insert(setting.name.clone()) {
+                errors.push(format!("The name {} is duplicated.", setting.name));
+            }
+
+            match parse_data_type(&setting.data_type) {
+                Some(data_type) => {
+                    if !data_types.insert(data_type as u32) {
+                        errors.push(format!("The data_type {} is duplicated.", setting.data_type));
+                    }
+                }
+                None => errors.push(format!("The data_type {} of {} is unknown.", setting.data_type, setting.name)),
+            }
+
+            if !data_dir.join(&setting.file).is_file() {
+                errors.push(format!("The file {} of {} does not exist.", setting.file, setting.name));
+            }
+
+            let mut statuses = vec![setting.status];
+            for (network, item) in setting.networks.iter() {
+                if let Err(err) = network.parse::<Network>() {
+                    errors.push(format!("The networks of {} is invalid: {}", setting.name, err));
+                }
+                statuses.extend(item.status);
+            }
+            if statuses.iter().any(|status| *status > 1) {
+                errors.push(format!("The status of {} should be 0 or 1.", setting.name));
+            }
+        }
+
+        errors
+    }
+
+    pub fn resolve(&self, network: Network) -> Vec<ResolvedCharSetSetting> {
+        self.char_sets
+            .iter()
+            .map(|setting| {
+                let mut status = setting.status;
+                let mut enabled = setting.enabled;
+                if let Some(item) = setting.networks.get(&network.to_string()) {
+                    status = item.status.unwrap_or(status);
+                    enabled = item.enabled.unwrap_or(enabled);
+                }
+
+                ResolvedCharSetSetting {
+                    name: setting.name.clone(),
+                    data_type: parse_data_type(&setting.data_type).unwrap(),
+                    file: setting.file.clone(),
+                    status,
+                    enabled,
+                }
+            })
+            .collect()
+    }
+}
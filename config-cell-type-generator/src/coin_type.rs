@@ -0,0 +1,94 @@
+use serde::Deserialize;
+use std::{collections::HashSet, fs, path::Path};
+
+use super::record_key::check_record_key;
+
+pub const COIN_TYPE_REGISTRY_FILE: &str = "coin_type_registry.toml";
+pub const ADDRESS_NAMESPACE: &str = "address";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CoinTypeRegistry {
+    pub coins: Vec<CoinType>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CoinType {
+    pub symbol: String,
+    pub coin_type: u32,
+    pub name: String,
+    pub key: Option<String>,
+}
+
+impl CoinType {
+    pub fn record_key(&self) -> String {
+        let key = self.key.clone().unwrap_or_else(|| self.symbol.to_lowercase());
+        format!("{}.{}", ADDRESS_NAMESPACE, key)
+    }
+}
+
+pub fn is_address_key(key: &str) -> bool {
+    key.starts_with(&format!("{}.", ADDRESS_NAMESPACE))
+}
+
+impl CoinTypeRegistry {
+    pub fn load(data_dir: &Path) -> Self {
+        let path = data_dir.join(COIN_TYPE_REGISTRY_FILE);
+        let data =
+            fs::read_to_string(&path).expect(format!("Expect file ./data/{} exist.", COIN_TYPE_REGISTRY_FILE).as_str());
+        let registry: CoinTypeRegistry = toml::from_str(&data)
+            .expect(format!("Expect file ./data/{} to be valid.", COIN_TYPE_REGISTRY_FILE).as_str());
+
+        let errors = registry.validate();
+        if !errors.is_empty() {
+            panic!("File {} is invalid:\n{}", COIN_TYPE_REGISTRY_FILE, errors.join("\n"));
+        }
+
+        registry
+    }
+
+    /// Check that every symbol, coin type and record key is unique and every record key is valid.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut symbols = HashSet::new();
+        let mut coin_types = HashSet::new();
+        let mut record_keys = HashSet::new();
+        for coin in self.coins.iter() {
+            if !symbols.insert(coin.symbol.clone()) {
+                errors.push(format!("The symbol {} is duplicated.", coin.symbol));
+            }
+            if !coin_types.insert(coin.coin_type) {
+                errors.push(format!(
+                    "The coin_type {} of {} is duplicated.",
+                    coin.coin_type, coin.symbol
+                ));
+            }
+
+            let record_key = coin.record_key();
+            if let Err(err) = check_record_key(&record_key) {
+                errors.push(format!("The key of {} is invalid: {}", coin.symbol, err));
+            }
+            if !record_keys.insert(record_key.clone()) {
+                errors.push(format!("The key {} of {} is duplicated.", record_key, coin.symbol));
+            }
+        }
+
+        errors
+    }
+
+    /// The address.* record keys in the order of the registry.
+    pub fn record_keys(&self) -> Vec<String> {
+        self.coins.iter().map(|coin| coin.record_key()).collect()
+    }
+}
+
+/// Replace the address.* keys with the generated ones, they are put where the first address.* key was, or appended if
+/// there is no address.* key.
+pub fn replace_address_keys(keys: &[String], address_keys: &[String]) -> Vec<String> {
+    let position = keys.iter().position(|key| is_address_key(key)).unwrap_or(keys.len());
+
+    let mut ret = keys[..position].to_vec();
+    ret.extend(address_keys.iter().cloned());
+    ret.extend(keys[position..].iter().filter(|key| !is_address_key(key)).cloned());
+
+    ret
+}
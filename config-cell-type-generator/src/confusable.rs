@@ -0,0 +1,112 @@
+use das_types_std::constants::*;
+use std::collections::{BTreeMap, HashMap};
+use unicode_security::skeleton;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::char_set::CharSet;
+
+/// Calculate the skeleton defined by UTS #39 with the confusables data bundled in unicode-security, characters which
+/// have the same skeleton are visually confusable.
+pub fn skeleton_of(char: &str) -> String {
+    skeleton(char).collect()
+}
+
+/// Find the characters from different char sets which are different but visually confusable.
+///
+/// Return a map from skeletons to the confusable characters and the char sets they come from.
+pub fn find_confusable_chars(char_sets: &[CharSet]) -> BTreeMap<String, Vec<(String, DataType)>> {
+    let mut groups: BTreeMap<String, Vec<(String, DataType)>> = BTreeMap::new();
+    for char_set in char_sets {
+        for char in char_set.chars.iter() {
+            groups
+                .entry(skeleton_of(char))
+                .or_default()
+                .push((char.clone(), char_set.data_type));
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|(_, chars)| {
+            chars
+                .iter()
+                .any(|(char, data_type)| chars.iter().any(|(c, t)| c != char && t != data_type))
+        })
+        .collect()
+}
+
+type SkeletonIndex<'a> = HashMap<String, Vec<&'a String>>;
+
+fn index_by_skeleton<'a>(char_sets: &[&'a CharSet]) -> SkeletonIndex<'a> {
+    let mut index: SkeletonIndex = HashMap::new();
+    for char_set in char_sets {
+        for char in char_set.chars.iter() {
+            index.entry(skeleton_of(char)).or_default().push(char);
+        }
+    }
+    index
+}
+
+/// Find the look-alikes of accounts which are made of characters from a language char set and the global char sets,
+/// like emoji and digit.
+pub struct LookAlikeFinder<'a> {
+    global: SkeletonIndex<'a>,
+    languages: Vec<(DataType, SkeletonIndex<'a>)>,
+}
+
+impl<'a> LookAlikeFinder<'a> {
+    pub fn new(char_sets: &'a [CharSet]) -> Self {
+        let global_sets = char_sets.iter().filter(|item| item.status == 1).collect::<Vec<_>>();
+        let languages = char_sets
+            .iter()
+            .filter(|item| item.status != 1)
+            .map(|item| (item.data_type, index_by_skeleton(&[item])))
+            .collect();
+
+        LookAlikeFinder {
+            global: index_by_skeleton(&global_sets),
+            languages,
+        }
+    }
+
+    /// Return the DataType of the language char set and the look-alike which replaces as many characters as possible.
+    ///
+    /// The account is split into grapheme clusters like char sets, so emoji sequences are matched as a whole. The
+    /// language char sets which the account itself is valid in are skipped.
+    pub fn find(&self, account: &str) -> Vec<(DataType, String)> {
+        let mut ret = Vec::new();
+        for (data_type, language) in self.languages.iter() {
+            let mut look_alike = String::new();
+            let mut found = true;
+            let mut is_valid = true;
+            for char in account.graphemes(true).map(|c| c.to_string()) {
+                let skeleton = skeleton_of(&char);
+                let mut candidates = self
+                    .global
+                    .get(&skeleton)
+                    .into_iter()
+                    .chain(language.get(&skeleton))
+                    .flatten()
+                    .collect::<Vec<_>>();
+                if candidates.is_empty() {
+                    found = false;
+                    break;
+                }
+
+                if !candidates.iter().any(|c| ***c == char) {
+                    is_valid = false;
+                }
+
+                // Prefer the characters which are different from the original one.
+                candidates.sort_by_key(|c| ***c == char);
+                look_alike.push_str(candidates[0]);
+            }
+
+            if found && !is_valid {
+                ret.push((*data_type, look_alike));
+            }
+        }
+
+        ret
+    }
+}
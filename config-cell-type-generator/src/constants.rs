@@ -1,3 +1,16 @@
 pub const WITNESS_SIZE_LIMIT: usize = 32_000;
 pub const ACCOUNT_ID_LENGTH: usize = 20;
 pub const PRESERVED_ACCOUNT_LIMIT_PER_CELL: usize = 10_000;
+// The witness of ConfigCell is "das" + 4 bytes of config type + entity.
+pub const WITNESS_HEADER_LENGTH: usize = 7;
+// The shard N of a char set whose DataType is T uses the config type T * 100 + N, and the index of the shards uses
+// T * 100 + 99, so a char set has 99 shards at most. These config types are not recognised by das-contracts yet, they are
+// only generated with the feature char-set-shards.
+pub const CHAR_SET_SHARD_CONFIG_TYPE_MULTIPLIER: u32 = 100;
+pub const CHAR_SET_SHARD_INDEX: u32 = CHAR_SET_SHARD_CONFIG_TYPE_MULTIPLIER - 1;
+// The namespaces of record keys, a key is like profile.twitter .
+pub const RECORD_KEY_NAMESPACES: [&str; 4] = ["profile", "address", "dweb", "custom_key"];
+pub const RECORD_KEY_MAX_LENGTH: usize = 64;
+// The record_min_ttl and record_size_limit of ConfigCellAccount.
+pub const RECORD_MIN_TTL: u32 = 300;
+pub const RECORD_SIZE_LIMIT: u32 = 5000;
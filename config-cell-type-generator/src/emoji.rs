@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::unicode_data::UnicodeData;
+
+const VARIATION_SELECTOR_TEXT: char = '\u{FE0E}';
+const VARIATION_SELECTOR_EMOJI: char = '\u{FE0F}';
+
+/// Check if an emoji is fully-qualified as defined by ED-18 in UTS #51, that is every emoji character in it is
+/// qualified:
+///
+/// - it has the default emoji presentation, or
+/// - it is followed by the emoji presentation selector FE0F, or
+/// - it is an emoji modifier base followed by an emoji modifier.
+pub fn is_fully_qualified(unicode: &UnicodeData, emoji: &str) -> bool {
+    let chars = emoji.chars().collect::<Vec<_>>();
+    chars.iter().enumerate().all(|(i, char)| {
+        let code_point = *char as u32;
+        if !unicode.has_property("Emoji", code_point) || unicode.has_property("Emoji_Presentation", code_point) {
+            return true;
+        }
+
+        match chars.get(i + 1) {
+            Some(next) if *next == VARIATION_SELECTOR_EMOJI => true,
+            Some(next) => {
+                unicode.has_property("Emoji_Modifier_Base", code_point)
+                    && unicode.has_property("Emoji_Modifier", *next as u32)
+            }
+            None => false,
+        }
+    })
+}
+
+/// The form of an emoji without variation selectors, like ☺ for ☺️.
+pub fn bare_form_of(emoji: &str) -> String {
+    emoji
+        .chars()
+        .filter(|char| *char != VARIATION_SELECTOR_TEXT && *char != VARIATION_SELECTOR_EMOJI)
+        .collect()
+}
+
+/// Check that every emoji is exactly one extended grapheme cluster and is fully-qualified, and that the bare form of
+/// an emoji is not in the char set too, otherwise an account can not be split into emoji unambiguously.
+pub fn check_emoji_chars(unicode: &UnicodeData, file: &str, chars: &[String]) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let all_chars = chars.iter().collect::<HashSet<_>>();
+    for char in chars {
+        let clusters = char.graphemes(true).count();
+        if clusters != 1 {
+            warnings.push(format!(
+                "{} emoji {} 0x{} is {} extended grapheme clusters, it should be exactly one",
+                file,
+                char,
+                hex::encode(char.as_bytes()),
+                clusters
+            ));
+        }
+
+        if !is_fully_qualified(unicode, char) {
+            warnings.push(format!(
+                "{} emoji {} 0x{} is not fully-qualified",
+                file,
+                char,
+                hex::encode(char.as_bytes())
+            ));
+        }
+
+        let bare_form = bare_form_of(char);
+        if &bare_form != char && all_chars.contains(&bare_form) {
+            warnings.push(format!(
+                "{} emoji {} 0x{} and its bare form 0x{} both exist",
+                file,
+                char,
+                hex::encode(char.as_bytes()),
+                hex::encode(bare_form.as_bytes())
+            ));
+        }
+    }
+
+    warnings
+}
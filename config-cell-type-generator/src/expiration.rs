@@ -0,0 +1,65 @@
+use super::util::format_timestamp;
+
+/// The duration of a year in the contracts, leap years are not considered.
+pub const ONE_YEAR: u64 = 365 * 86400;
+/// The expiration_grace_period of ConfigCellAccount.
+pub const EXPIRATION_GRACE_PERIOD: u64 = 7_776_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AccountStatus {
+    Active,
+    /// Expired but the owner can still renew it.
+    InGracePeriod,
+    /// The grace period has ended, anyone can recycle the account and register it again.
+    Recyclable,
+}
+
+/// The key times of an account in unix timestamps, they are calculated from the expired_at of AccountCell.
+#[derive(Debug, Clone, Copy)]
+pub struct Expiration {
+    pub expired_at: u64,
+    pub grace_period_ends_at: u64,
+    /// The contracts only recycle an account after the grace period ends, so it is 1 second later than
+    /// grace_period_ends_at.
+    pub re_registrable_at: u64,
+}
+
+impl Expiration {
+    pub fn new(expired_at: u64) -> Self {
+        let grace_period_ends_at = expired_at + EXPIRATION_GRACE_PERIOD;
+        Expiration {
+            expired_at,
+            grace_period_ends_at,
+            re_registrable_at: grace_period_ends_at + 1,
+        }
+    }
+
+    /// The expired_at of an account registered at the time for some years.
+    pub fn from_registration(registered_at: u64, years: u64) -> Self {
+        Expiration::new(registered_at + years * ONE_YEAR)
+    }
+
+    /// Renew the account for some years, the years are added to the expired_at no matter when it is renewed, and it
+    /// can not be renewed after the grace period ends.
+    pub fn renew(&self, years: u64, renewed_at: u64) -> Result<Self, String> {
+        if renewed_at > self.grace_period_ends_at {
+            return Err(format!(
+                "The account can not be renewed at {}, because its grace period ends at {}.",
+                format_timestamp(renewed_at),
+                format_timestamp(self.grace_period_ends_at)
+            ));
+        }
+
+        Ok(Expiration::new(self.expired_at + years * ONE_YEAR))
+    }
+
+    pub fn status_at(&self, timestamp: u64) -> AccountStatus {
+        if timestamp <= self.expired_at {
+            AccountStatus::Active
+        } else if timestamp <= self.grace_period_ends_at {
+            AccountStatus::InGracePeriod
+        } else {
+            AccountStatus::Recyclable
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+use super::price::RATE_BASE;
+
+/// The parameters of IncomeCells in ConfigCellIncome.
+#[derive(Debug, Clone)]
+pub struct IncomeConfig {
+    pub basic_capacity: u64,
+    pub max_records: usize,
+    pub min_transfer_capacity: u64,
+}
+
+pub const INCOME_CONFIG: IncomeConfig = IncomeConfig {
+    basic_capacity: 20_000_000_000,
+    max_records: 50,
+    min_transfer_capacity: 12_000_000_000,
+};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IncomeCell {
+    pub records: Vec<IncomeRecord>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IncomeRecord {
+    /// The owner lock in hex, it is only compared.
+    pub lock: String,
+    pub capacity: u64,
+}
+
+/// Load IncomeCells like [{"records": [{"lock": "0x...", "capacity": 20000000000}]}] from a JSON file.
+pub fn load_income_cells(path: &Path) -> Vec<IncomeCell> {
+    let data = fs::read_to_string(path).expect(format!("Expect file {} exist.", path.display()).as_str());
+    serde_json::from_str(&data).expect(format!("Expect file {} to be valid IncomeCells.", path.display()).as_str())
+}
+
+#[derive(Debug, Clone)]
+pub struct Transfer {
+    pub lock: String,
+    /// The capacity transferred to the owner after the fee.
+    pub capacity: u64,
+    pub fee: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConsolidationPlan {
+    pub transfers: Vec<Transfer>,
+    /// The records which stay in new IncomeCells.
+    pub income_cells: Vec<Vec<IncomeRecord>>,
+    /// The capacity the consolidator adds to make the IncomeCells reach basic_capacity.
+    pub padding: u64,
+}
+
+impl ConsolidationPlan {
+    pub fn fee(&self) -> u64 {
+        self.transfers.iter().map(|item| item.fee).sum()
+    }
+
+    /// The outputs except the change of the consolidator, which also receives the fee.
+    pub fn output_count(&self) -> usize {
+        self.transfers.len() + self.income_cells.len()
+    }
+}
+
+/// Check that every IncomeCell has records no more than max_records and capacity no less than basic_capacity.
+pub fn check_income_cells(config: &IncomeConfig, cells: &[IncomeCell]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (i, cell) in cells.iter().enumerate() {
+        if cell.records.len() > config.max_records {
+            errors.push(format!(
+                "IncomeCell #{} has {} records, it should be no more than max_records {}.",
+                i + 1,
+                cell.records.len(),
+                config.max_records
+            ));
+        }
+        let capacity = cell.records.iter().map(|record| record.capacity).sum::<u64>();
+        if capacity < config.basic_capacity {
+            errors.push(format!(
+                "IncomeCell #{} has {} shannons, it should be no less than basic_capacity {}.",
+                i + 1,
+                capacity,
+                config.basic_capacity
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Plan the consolidation of IncomeCells:
+///
+/// - The records of the same lock are merged.
+/// - The merged records no less than min_transfer_capacity are transferred to their owners, the consolidator takes
+///   income_consolidate of them as fee except for its own records.
+/// - The other records stay in new IncomeCells with no more than max_records records in each, and the consolidator
+///   pads every IncomeCell below basic_capacity with a record of its own.
+pub fn plan_consolidation(
+    config: &IncomeConfig,
+    income_consolidate: u32,
+    consolidator: &str,
+    cells: &[IncomeCell],
+) -> ConsolidationPlan {
+    let mut merged: Vec<IncomeRecord> = Vec::new();
+    for record in cells.iter().flat_map(|cell| cell.records.iter()) {
+        match merged.iter_mut().find(|item| item.lock == record.lock) {
+            Some(item) => item.capacity += record.capacity,
+            None => merged.push(record.clone()),
+        }
+    }
+
+    let mut plan = ConsolidationPlan::default();
+    let mut kept = Vec::new();
+    for record in merged {
+        if record.capacity < config.min_transfer_capacity {
+            kept.push(record);
+            continue;
+        }
+
+        let fee = if record.lock == consolidator {
+            0
+        } else {
+            record.capacity * income_consolidate as u64 / RATE_BASE
+        };
+        plan.transfers.push(Transfer {
+            lock: record.lock,
+            capacity: record.capacity - fee,
+            fee,
+        });
+    }
+
+    // Every IncomeCell keeps a slot for the record of the consolidator, so it can always be padded.
+    let records_per_cell = config.max_records.saturating_sub(1).max(1);
+    for records in kept.chunks(records_per_cell) {
+        let mut records = records.to_vec();
+        let capacity = records.iter().map(|record| record.capacity).sum::<u64>();
+        if capacity < config.basic_capacity {
+            let padding = config.basic_capacity - capacity;
+            match records.iter_mut().find(|record| record.lock == consolidator) {
+                Some(record) => record.capacity += padding,
+                None => records.push(IncomeRecord {
+                    lock: consolidator.to_string(),
+                    capacity: padding,
+                }),
+            }
+            plan.padding += padding;
+        }
+        plan.income_cells.push(records);
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_consolidation_transfers_merged_records() {
+        let cells = vec![
+            IncomeCell {
+                records: vec![
+                    IncomeRecord {
+                        lock: String::from("0xa"),
+                        capacity: 10_000_000_000,
+                    },
+                    IncomeRecord {
+                        lock: String::from("0xconsolidator"),
+                        capacity: 20_000_000_000,
+                    },
+                ],
+            },
+            IncomeCell {
+                records: vec![
+                    IncomeRecord {
+                        lock: String::from("0xa"),
+                        capacity: 10_000_000_000,
+                    },
+                    IncomeRecord {
+                        lock: String::from("0xb"),
+                        capacity: 1_000_000_000,
+                    },
+                ],
+            },
+        ];
+        let plan = plan_consolidation(&INCOME_CONFIG, 500, "0xconsolidator", &cells);
+
+        // The records of 0xa are merged to 200 CKB and 5% of them is the fee, the consolidator pays no fee.
+        assert_eq!(plan.transfers.len(), 2);
+        assert_eq!(plan.transfers[0].lock, "0xa");
+        assert_eq!(plan.transfers[0].capacity, 19_000_000_000);
+        assert_eq!(plan.transfers[0].fee, 1_000_000_000);
+        assert_eq!(plan.transfers[1].lock, "0xconsolidator");
+        assert_eq!(plan.transfers[1].fee, 0);
+        assert_eq!(plan.fee(), 1_000_000_000);
+
+        // The record of 0xb is too small to transfer, so it stays and the consolidator pads the IncomeCell.
+        assert_eq!(plan.income_cells.len(), 1);
+        assert_eq!(plan.padding, 19_000_000_000);
+        assert_eq!(plan.output_count(), 3);
+    }
+
+    #[test]
+    fn test_plan_consolidation_keeps_a_slot_for_padding() {
+        let config = IncomeConfig {
+            max_records: 3,
+            ..INCOME_CONFIG
+        };
+        let cells = vec![IncomeCell {
+            records: vec![
+                IncomeRecord {
+                    lock: String::from("0xa"),
+                    capacity: 1_000_000_000,
+                },
+                IncomeRecord {
+                    lock: String::from("0xb"),
+                    capacity: 1_000_000_000,
+                },
+                IncomeRecord {
+                    lock: String::from("0xc"),
+                    capacity: 1_000_000_000,
+                },
+            ],
+        }];
+        let plan = plan_consolidation(&config, 500, "0xconsolidator", &cells);
+
+        assert!(plan.transfers.is_empty());
+        assert_eq!(plan.income_cells.len(), 2);
+        for records in plan.income_cells.iter() {
+            assert!(records.len() <= config.max_records);
+            assert_eq!(
+                records.iter().map(|item| item.capacity).sum::<u64>(),
+                config.basic_capacity
+            );
+        }
+        assert_eq!(plan.padding, 2 * config.basic_capacity - 3_000_000_000);
+        assert!(check_income_cells(
+            &config,
+            &plan
+                .income_cells
+                .iter()
+                .map(|records| IncomeCell {
+                    records: records.clone()
+                })
+                .collect::<Vec<_>>()
+        )
+        .is_empty());
+    }
+}
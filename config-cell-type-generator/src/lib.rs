@@ -0,0 +1,24 @@
+//! The char set modules are shared with the other generators, like the segmentation of accounts in
+//! account-cell-type-generator.
+
+pub mod auction;
+pub mod capacity;
+pub mod char_set;
+pub mod char_set_bundle;
+pub mod char_set_recipe;
+pub mod char_set_registry;
+pub mod coin_type;
+pub mod confusable;
+pub mod constants;
+pub mod emoji;
+pub mod expiration;
+pub mod income;
+pub mod network;
+pub mod price;
+pub mod profit_rate;
+pub mod record_key;
+pub mod records;
+pub mod release;
+pub mod secondary_market;
+pub mod unicode_data;
+pub mod util;
@@ -1,16 +1,25 @@
 #![allow(dead_code)]
 
-use std::convert::TryFrom;
+use std::{
+    convert::TryFrom,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use ckb_hash::blake2b_256;
+use clap::Clap;
+use config_cell_type_generator::{
+    auction, capacity, char_set, char_set_bundle, char_set_recipe, char_set_registry, coin_type, confusable, constants,
+    expiration, income, network, price, profit_rate, record_key, records, release, secondary_market, unicode_data,
+    util,
+};
 use das_types_std::{constants::*, packed::*, prelude::*, util as das_util};
 use faster_hex::hex_string;
 use util::{gen_price_config, prepend_molecule_like_length, read_lines};
 
-mod constants;
-mod util;
 use constants::*;
 use hex;
+use network::Network;
 
 macro_rules! out_point {
     ($tx_hash:expr, $index:expr) => {
@@ -81,15 +90,39 @@ macro_rules! gen_return_from_raw {
     }};
 }
 
+/// The same as gen_return_from_raw, but it supports config types which are not defined in DataType, like the shards
+/// of char sets.
+fn gen_return_from_raw_config_type(config_type: u32, entity: Vec<u8>) -> String {
+    let cell_data = blake2b_256(entity.as_slice());
+    let action_witness = das_util::wrap_action_witness("config", None);
+
+    let cell_witness = util::wrap_config_witness(config_type, &entity);
+
+    if cell_witness.len() > WITNESS_SIZE_LIMIT {
+        panic!(
+            "The size of {} is more than {} bytes, this needs to modify das-contracts to support.",
+            config_type, WITNESS_SIZE_LIMIT
+        )
+    }
+
+    format!(
+        "0x{} 0x{} 0x{} 0x{}",
+        hex_string(&config_type.to_le_bytes()),
+        hex_string(&cell_data),
+        hex_string(action_witness.as_reader().raw_data()),
+        hex_string(&cell_witness),
+    )
+}
+
 fn gen_config_cell_account() -> String {
     let entity = ConfigCellAccount::new_builder()
         .max_length(Uint32::from(42))
         // The basic_capacity contains 1 CKB for kinds of fees
-        .basic_capacity(Uint64::from(20_600_000_000))
-        .prepared_fee_capacity(Uint64::from(100_000_000))
-        .expiration_grace_period(Uint32::from(7_776_000))
-        .record_min_ttl(Uint32::from(300))
-        .record_size_limit(Uint32::from(5000))
+        .basic_capacity(Uint64::from(capacity::ACCOUNT_CELL_BASIC_CAPACITY))
+        .prepared_fee_capacity(Uint64::from(capacity::ACCOUNT_CELL_PREPARED_FEE_CAPACITY))
+        .expiration_grace_period(Uint32::from(expiration::EXPIRATION_GRACE_PERIOD as u32))
+        .record_min_ttl(Uint32::from(RECORD_MIN_TTL))
+        .record_size_limit(Uint32::from(RECORD_SIZE_LIMIT))
         .transfer_account_fee(Uint64::from(20_000))
         .edit_manager_fee(Uint64::from(20_000))
         .edit_records_fee(Uint64::from(20_000))
@@ -113,10 +146,11 @@ fn gen_config_cell_apply() -> String {
 }
 
 fn gen_config_cell_income() -> String {
+    let income = income::INCOME_CONFIG;
     let entity = ConfigCellIncome::new_builder()
-        .basic_capacity(Uint64::from(20_000_000_000))
-        .max_records(Uint32::from(50))
-        .min_transfer_capacity(Uint64::from(12_000_000_000))
+        .basic_capacity(Uint64::from(income.basic_capacity))
+        .max_records(Uint32::from(income.max_records as u32))
+        .min_transfer_capacity(Uint64::from(income.min_transfer_capacity))
         .build();
 
     gen_return_from_entity!(DataType::ConfigCellIncome, entity)
@@ -173,32 +207,14 @@ fn gen_config_cell_main() -> String {
 
 fn gen_config_cell_price() -> String {
     let discount = DiscountConfig::new_builder()
-        .invited_discount(Uint32::from(500))
+        .invited_discount(Uint32::from(price::INVITED_DISCOUNT))
         .build();
 
-    #[cfg(feature = "mainnet")]
-    let prices = PriceConfigList::new_builder()
-        .push(gen_price_config(1, 1_000_000, 1_000_000))
-        .push(gen_price_config(2, 1024_000_000, 1024_000_000))
-        .push(gen_price_config(3, 660_000_000, 660_000_000))
-        .push(gen_price_config(4, 160_000_000, 160_000_000))
-        .push(gen_price_config(5, 5_000_000, 5_000_000))
-        .push(gen_price_config(6, 5_000_000, 5_000_000))
-        .push(gen_price_config(7, 5_000_000, 5_000_000))
-        .push(gen_price_config(8, 5_000_000, 5_000_000))
-        .build();
-
-    #[cfg(not(feature = "mainnet"))]
-    let prices = PriceConfigList::new_builder()
-        .push(gen_price_config(1, 1_000_000, 1_000_000))
-        .push(gen_price_config(2, 30_000_000, 30_000_000))
-        .push(gen_price_config(3, 20_000_000, 20_000_000))
-        .push(gen_price_config(4, 10_000_000, 10_000_000))
-        .push(gen_price_config(5, 5_000_000, 5_000_000))
-        .push(gen_price_config(6, 5_000_000, 5_000_000))
-        .push(gen_price_config(7, 5_000_000, 5_000_000))
-        .push(gen_price_config(8, 5_000_000, 5_000_000))
-        .build();
+    let mut prices = PriceConfigList::new_builder();
+    for item in price::prices_of(Network::current()) {
+        prices = prices.push(gen_price_config(item.length, item.new, item.renew));
+    }
+    let prices = prices.build();
 
     let entity = ConfigCellPrice::new_builder().discount(discount).prices(prices).build();
 
@@ -218,32 +234,36 @@ fn gen_config_cell_proposal() -> String {
 }
 
 fn gen_config_cell_profit_rate() -> String {
+    let rate = profit_rate::PROFIT_RATE;
     let entity = ConfigCellProfitRate::new_builder()
-        .channel(Uint32::from(1000))
-        .inviter(Uint32::from(1000))
-        .proposal_create(Uint32::from(200))
-        .proposal_confirm(Uint32::from(0))
-        .income_consolidate(Uint32::from(500))
-        .sale_buyer_inviter(Uint32::from(100))
-        .sale_buyer_channel(Uint32::from(150))
-        .sale_das(Uint32::from(100))
-        .auction_bidder_inviter(Uint32::from(100))
-        .auction_bidder_channel(Uint32::from(100))
-        .auction_das(Uint32::from(100))
-        .auction_prev_bidder(Uint32::from(4700))
+        .channel(Uint32::from(rate.channel))
+        .inviter(Uint32::from(rate.inviter))
+        .proposal_create(Uint32::from(rate.proposal_create))
+        .proposal_confirm(Uint32::from(rate.proposal_confirm))
+        .income_consolidate(Uint32::from(rate.income_consolidate))
+        .sale_buyer_inviter(Uint32::from(rate.sale_buyer_inviter))
+        .sale_buyer_channel(Uint32::from(rate.sale_buyer_channel))
+        .sale_das(Uint32::from(rate.sale_das))
+        .auction_bidder_inviter(Uint32::from(rate.auction_bidder_inviter))
+        .auction_bidder_channel(Uint32::from(rate.auction_bidder_channel))
+        .auction_das(Uint32::from(rate.auction_das))
+        .auction_prev_bidder(Uint32::from(rate.auction_prev_bidder))
         .build();
 
     gen_return_from_entity!(DataType::ConfigCellProfitRate, entity)
 }
 
 fn gen_config_cell_record_key_namespace() -> String {
-    let mut record_key_namespace = Vec::new();
-    let lines = read_lines("record_key_namespace.txt").expect("Expect file ./data/record_key_namespace.txt exist.");
-    for line in lines {
-        if let Ok(key) = line {
-            record_key_namespace.push(key);
-        }
+    let record_keys = record_key::load_record_keys(&util::data_dir(), record_key::RECORD_KEY_NAMESPACE_FILE);
+    if !record_keys.errors.is_empty() {
+        panic!(
+            "File ./data/{} is invalid:\n{}",
+            record_key::RECORD_KEY_NAMESPACE_FILE,
+            record_keys.errors.join("\n")
+        );
     }
+
+    let mut record_key_namespace = record_keys.keys;
     record_key_namespace.sort();
     // println!("record_key_namespace: \n{}", record_key_namespace.join("\n"));
 
@@ -296,78 +316,598 @@ fn gen_config_cell_reserved_account() -> String {
 }
 
 fn gen_config_cell_char_set() -> String {
-    let settings: Vec<(DataType, &str, u8)> = vec![
-        (DataType::ConfigCellCharSetEmoji, "char_set_emoji.txt", 1),
-        (DataType::ConfigCellCharSetDigit, "char_set_digit_and_symbol.txt", 1),
-        (DataType::ConfigCellCharSetEn, "char_set_en.txt", 0),
-        // (DataType::ConfigCellCharSetZhHans, "char_set_zh_hans.txt", 0),
-        // (DataType::ConfigCellCharSetZhHant, "char_set_zh_hant.txt", 0),
-        (DataType::ConfigCellCharSetJa, "char_set_ja.txt", 0),
-        (DataType::ConfigCellCharSetKo, "char_set_ko.txt", 0),
-        // (DataType::ConfigCellCharSetRu, "char_set_ru.txt", 0),
-        (DataType::ConfigCellCharSetTr, "char_set_tr.txt", 0),
-        (DataType::ConfigCellCharSetTh, "char_set_th.txt", 0),
-        (DataType::ConfigCellCharSetVi, "char_set_vi.txt", 0),
-
-    ];
+    let char_sets = char_set::load_char_sets(&util::data_dir(), Network::current());
 
     let mut output = String::new();
     let mut comma = "";
-    // let mut dedup_chars = Vec::new();
-    for (_i, setting) in settings.iter().enumerate() {
-        let mut charsets = Vec::new();
-        let lines = read_lines(setting.1).expect(format!("Expect file ./data/{} exist.", setting.1).as_str());
+    for char_set in char_sets {
+        for warning in char_set.warnings.iter() {
+            eprintln!("⚠️ {}", warning);
+        }
+
+        // println!("Character count of {:?}: {}", char_set.data_type, char_set.chars.len());
+
+        let raw = char_set::char_set_raw(char_set.status, &char_set.chars);
+        if cfg!(feature = "char-set-shards") && char_set::exceeds_witness_limit(&raw) {
+            // The char set which is too large for one ConfigCell is split into shards and an index of them.
+            for (config_type, entity) in char_set::char_set_entities(&char_set) {
+                output += comma;
+                output += gen_return_from_raw_config_type(config_type, entity).as_str();
+                comma = ",";
+            }
+        } else {
+            output += comma;
+            output += gen_return_from_raw!(char_set.data_type, prepend_molecule_like_length(raw)).as_str();
+            comma = ",";
+        }
+    }
+
+    output
+}
+
+fn check_char_sets() {
+    let char_sets = char_set::load_char_sets(&util::data_dir(), Network::current());
+
+    let mut count = 0;
+    for char_set in char_sets.iter() {
+        println!("{:?} {}: {} characters", char_set.data_type, char_set.file, char_set.chars.len());
+        for warning in char_set.warnings.iter() {
+            println!("  ⚠️ {}", warning);
+        }
+        count += char_set.warnings.len();
+    }
+    println!();
+
+    let duplicates = char_set::find_cross_duplicates(&char_sets);
+    println!("Characters exist in more than one char set: {}", duplicates.len());
+    for (char, data_types) in duplicates {
+        println!("  {} 0x{} {:?}", char, hex::encode(char.as_bytes()), data_types);
+    }
+
+    if count > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn check_confusables(options: CheckConfusablesOptions) {
+    let char_sets = if options.all {
+        char_set::load_all_char_sets(&util::data_dir(), Network::current())
+    } else {
+        char_set::load_char_sets(&util::data_dir(), Network::current())
+    };
+
+    let confusables = confusable::find_confusable_chars(&char_sets);
+    println!("Confusable characters across char sets: {}", confusables.len());
+    for (skeleton, chars) in confusables {
+        let chars = chars
+            .iter()
+            .map(|(char, data_type)| format!("{} 0x{} {:?}", char, hex::encode(char.as_bytes()), data_type))
+            .collect::<Vec<_>>();
+        println!("  {} => {}", skeleton, chars.join(", "));
+    }
+    println!();
+
+    let mut accounts = options
+        .accounts
+        .iter()
+        .map(|account| account.trim_end_matches(".bit").to_string())
+        .collect::<Vec<_>>();
+    if options.reserved {
+        let lines = read_lines("reserved_accounts.txt").expect("Expect file ./data/reserved_accounts.txt exist.");
         for line in lines {
-            if let Ok(char) = line {
-                let cleared_char = char.trim().to_string();
-                if cleared_char.is_empty() {
-                    continue;
-                }
-                if cleared_char.as_bytes().contains(&0u8) {
-                    // CAREFUL! Characters which contains 0x00 are not allowed, so it exists warn the developer to review the config file.
-                    panic!("File {} character {} contains 0x00 byte.", setting.1, cleared_char);
-                }
-                // if dedup_chars.contains(&char) {
-                //     println!("{} find duplicated char: {} 0x{}", setting.1, char, hex::encode(char.as_bytes()));
-                // } else {
-                //     dedup_chars.push(char.clone());
-                // }
-                charsets.push(cleared_char);
+            if let Ok(account) = line {
+                accounts.push(account);
             }
         }
+    }
 
-        // println!("Character count of {:?}: {}", setting.0, charsets.len());
+    if accounts.is_empty() {
+        return;
+    }
 
-        // Join all record keys with 0x00 byte as entity.
-        let mut raw: Vec<u8> = Vec::new();
-        raw.push(setting.2); // global status
-        for key in charsets {
-            raw.extend(key.as_bytes());
-            raw.extend(&[0u8]);
+    let finder = confusable::LookAlikeFinder::new(&char_sets);
+    let mut count = 0;
+    println!("Accounts which have look-alikes in other char sets:");
+    for account in accounts {
+        for (data_type, look_alike) in finder.find(&account) {
+            println!(
+                "  {}.bit => {}.bit 0x{} {:?}",
+                account,
+                look_alike,
+                hex::encode(look_alike.as_bytes()),
+                data_type
+            );
+            count += 1;
         }
-        let raw = prepend_molecule_like_length(raw);
+    }
+    println!("Total: {}", count);
+}
 
-        output += comma;
-        output += gen_return_from_raw!(setting.0, raw).as_str();
-        comma = ",";
+fn gen_char_sets(options: GenCharSetsOptions) {
+    let data_dir = util::data_dir();
+    let unicode = unicode_data::UnicodeData::load(&data_dir);
+    for file in unicode.derived_files.iter() {
+        eprintln!("⚠️ ./data/{} is used because the official file is not in ./data/unicode .", file);
+    }
+    let settings = char_set_registry::CharSetRegistry::load(&data_dir).resolve(Network::current());
+    for name in options.names.iter() {
+        if !settings.iter().any(|setting| &setting.name == name) {
+            panic!("The char set {} is not declared in ./data/{}.", name, char_set_registry::CHAR_SET_REGISTRY_FILE);
+        }
     }
 
-    output
+    let mut count = 0;
+    for setting in settings.iter() {
+        if !options.names.is_empty() && !options.names.contains(&setting.name) {
+            continue;
+        }
+
+        let recipe = match char_set_recipe::CharSetRecipe::load(&data_dir, &setting.file, &unicode) {
+            Some(recipe) => recipe,
+            None => {
+                println!("{}: no recipe, skipped", setting.file);
+                continue;
+            }
+        };
+        let chars = recipe.build(&data_dir, &unicode);
+        let current = char_set::load_file_chars(&data_dir, &setting.file);
+        if chars == current {
+            println!("{}: {} characters, up to date", setting.file, chars.len());
+            continue;
+        }
+
+        let missing = current.iter().filter(|char| !chars.contains(char)).collect::<Vec<_>>();
+        let extra = chars.iter().filter(|char| !current.contains(char)).collect::<Vec<_>>();
+        println!(
+            "{}: {} characters, {} added, {} removed{}",
+            setting.file,
+            chars.len(),
+            extra.len(),
+            missing.len(),
+            if missing.is_empty() && extra.is_empty() {
+                ", only the order is changed"
+            } else {
+                ""
+            }
+        );
+        for char in extra {
+            println!("  + {} 0x{}", char, hex::encode(char.as_bytes()));
+        }
+        for char in missing {
+            println!("  - {} 0x{}", char, hex::encode(char.as_bytes()));
+        }
+        count += 1;
+
+        if !options.check {
+            let mut data = chars.join("\n");
+            data.push('\n');
+            std::fs::write(data_dir.join(&setting.file), data)
+                .expect(format!("Expect file ./data/{} to be writable.", setting.file).as_str());
+        }
+    }
+
+    if options.check && count > 0 {
+        std::process::exit(1);
+    }
 }
 
-///0x6d000000
-fn gen_config_cell_release() -> String {
-    // release to 40% = 1717986918
-    // release to 45% = 1932735282
-    // release to 50% = 2147483647
-    // release to 55% = 2362232012
-    // release to 60% = 2576980377
+fn export_char_sets(options: ExportCharSetsOptions) {
+    let char_sets = char_set::load_char_sets(&util::data_dir(), Network::current());
+    for char_set in char_sets.iter() {
+        for warning in char_set.warnings.iter() {
+            eprintln!("⚠️ {}", warning);
+        }
+    }
+
+    let bundle = char_set_bundle::CharSetBundle::new(Network::current(), &char_sets);
+    let data = serde_json::to_string_pretty(&bundle).expect("Expect the char set bundle to be serializable.");
+    match options.output {
+        Some(output) => {
+            std::fs::write(&output, data + "\n").expect(format!("Expect file {} to be writable.", output).as_str())
+        }
+        None => println!("{}", data),
+    }
+}
+
+fn check_record_keys(options: CheckRecordKeysOptions) {
+    let record_keys = record_key::load_record_keys(&util::data_dir(), record_key::RECORD_KEY_NAMESPACE_FILE);
+    println!("{}: {} keys", record_key::RECORD_KEY_NAMESPACE_FILE, record_keys.keys.len());
+    for (namespace, count) in record_keys.count_by_namespace() {
+        println!("  {}: {}", namespace, count);
+    }
+    for err in record_keys.errors.iter() {
+        println!("  ⚠️ {}", err);
+    }
+
+    if let Some(previous) = options.previous {
+        let previous_keys = record_key::load_record_keys(Path::new(""), &previous);
+        let (added, removed) = record_key::diff_record_keys(&record_keys.keys, &previous_keys.keys);
+        println!();
+        println!("Keys added since the previous release: {}", added.len());
+        for key in added {
+            println!("  + {}", key);
+        }
+        println!("Keys removed since the previous release: {}", removed.len());
+        for key in removed {
+            println!("  - {}", key);
+        }
+    }
 
-    #[cfg(feature = "mainnet")]
-    let lucky_number = 2576980377;
+    if !record_keys.errors.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn gen_address_keys(options: GenAddressKeysOptions) {
+    let data_dir = util::data_dir();
+    let address_keys = coin_type::CoinTypeRegistry::load(&data_dir).record_keys();
+    let record_keys = record_key::load_record_keys(&data_dir, record_key::RECORD_KEY_NAMESPACE_FILE);
+    if !record_keys.errors.is_empty() {
+        panic!(
+            "File ./data/{} is invalid:\n{}",
+            record_key::RECORD_KEY_NAMESPACE_FILE,
+            record_keys.errors.join("\n")
+        );
+    }
+
+    let current_address_keys = record_keys
+        .keys
+        .iter()
+        .filter(|key| coin_type::is_address_key(key))
+        .cloned()
+        .collect::<Vec<_>>();
+    let (added, removed) = record_key::diff_record_keys(&address_keys, &current_address_keys);
+    println!("Coins in the registry which have no address.* key: {}", added.len());
+    for key in added {
+        println!("  + {}", key);
+    }
+    println!("Address.* keys which have no coin in the registry: {}", removed.len());
+    for key in removed {
+        println!("  - {}", key);
+    }
+
+    let keys = coin_type::replace_address_keys(&record_keys.keys, &address_keys);
+    if keys == record_keys.keys {
+        println!("{} is up to date.", record_key::RECORD_KEY_NAMESPACE_FILE);
+        return;
+    }
+
+    if options.check {
+        println!("{} does not match the coin type registry.", record_key::RECORD_KEY_NAMESPACE_FILE);
+        std::process::exit(1);
+    }
+
+    let mut data = keys.join("\n");
+    data.push('\n');
+    std::fs::write(data_dir.join(record_key::RECORD_KEY_NAMESPACE_FILE), data)
+        .expect(format!("Expect file ./data/{} to be writable.", record_key::RECORD_KEY_NAMESPACE_FILE).as_str());
+    println!("{} is updated.", record_key::RECORD_KEY_NAMESPACE_FILE);
+}
+
+fn check_records(options: CheckRecordsOptions) {
+    let namespace = record_key::load_record_keys(&util::data_dir(), record_key::RECORD_KEY_NAMESPACE_FILE).keys;
+    let records = records::load_records(Path::new(&options.records));
+
+    let errors = records::check_records(&records, &namespace);
+    for err in errors.iter() {
+        println!("⚠️ {}", err);
+    }
+
+    let entity = records::gen_records(&records);
+    println!("Records: {} records, {} bytes", records.len(), entity.as_slice().len());
+    println!("0x{}", hex_string(entity.as_slice()));
+
+    if !errors.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn calc_profit(options: ProfitOptions) {
+    let event = options
+        .event
+        .parse::<profit_rate::ProfitEvent>()
+        .unwrap_or_else(|err| panic!("{}", err));
+    let rate = match options.rates {
+        Some(path) => profit_rate::ProfitRate::load(Path::new(&path)),
+        None => profit_rate::PROFIT_RATE,
+    };
 
-    #[cfg(not(feature = "mainnet"))]
-    let lucky_number = 2576980377;
+    let errors = rate.validate();
+    if !errors.is_empty() {
+        for err in errors.iter() {
+            println!("⚠️ {}", err);
+        }
+        std::process::exit(1);
+    }
+
+    if options.previous_price.is_some() && event != profit_rate::ProfitEvent::Auction {
+        panic!("--previous-price only works with --event=auction.");
+    }
+
+    let shares = match event {
+        // The bids of auctions are split the same way as simulate-auction.
+        profit_rate::ProfitEvent::Auction => profit_rate::distribute_bid(
+            &rate,
+            options.previous_price,
+            options.amount,
+            !options.no_inviter,
+            !options.no_channel,
+        ),
+        _ => profit_rate::distribute_profit(&rate, event, options.amount, !options.no_inviter, !options.no_channel),
+    };
+    println!("Event: {}, amount: {} shannons", event, options.amount);
+    if let Some(previous_price) = options.previous_price {
+        println!(
+            "The previous bidder is refunded {} shannons, the increment {} shannons is split.",
+            previous_price,
+            options.amount - previous_price
+        );
+    }
+    for share in shares.iter() {
+        let rate = match share.rate {
+            Some(rate) => format!("{}/{}", rate, price::RATE_BASE),
+            None => String::from("remainder"),
+        };
+        println!("  {:<20} {:>12} {:>20}", share.party.to_string(), rate, share.amount);
+    }
+}
+
+fn check_capacities(options: CheckCapacitiesOptions) {
+    let mut errors = Vec::new();
+    println!(
+        "{:<20} {:>16} {:>16} {:>16} {:>16}",
+        "cell", "occupied", "prepared fee", "basic", "margin"
+    );
+    for cell in capacity::cell_capacities(options.account_size) {
+        let required = cell.occupied_capacity() + cell.prepared_fee_capacity;
+        let margin = match cell.basic_capacity.checked_sub(required) {
+            Some(margin) => capacity::format_ckb(margin),
+            None => format!("-{}", capacity::format_ckb(required - cell.basic_capacity)),
+        };
+        println!(
+            "{:<20} {:>16} {:>16} {:>16} {:>16}",
+            cell.name,
+            capacity::format_ckb(cell.occupied_capacity()),
+            capacity::format_ckb(cell.prepared_fee_capacity),
+            capacity::format_ckb(cell.basic_capacity),
+            margin
+        );
+
+        if let Err(err) = cell.check() {
+            errors.push(err);
+        }
+    }
+
+    if !errors.is_empty() {
+        println!();
+        for err in errors.iter() {
+            println!("⚠️ {}", err);
+        }
+        std::process::exit(1);
+    }
+}
+
+fn simulate_auction(options: SimulateAuctionOptions) {
+    let script = auction::AuctionScript::load(Path::new(&options.script));
+    let rate = match options.rates {
+        Some(path) => profit_rate::ProfitRate::load(Path::new(&path)),
+        None => profit_rate::PROFIT_RATE,
+    };
+
+    let mut errors = auction::check_auction_script(&auction::AUCTION_CONFIG, &script);
+    errors.extend(rate.validate());
+    if !errors.is_empty() {
+        for err in errors.iter() {
+            println!("⚠️ {}", err);
+        }
+        std::process::exit(1);
+    }
+
+    let result = auction::simulate_auction(&auction::AUCTION_CONFIG, &rate, &script);
+    println!(
+        "Auction: opening price {} CKB, started at {}, ends at {}",
+        capacity::format_ckb(script.opening_price),
+        script.started_at,
+        script.started_at + script.duration
+    );
+    for (i, item) in result.bids.iter().enumerate() {
+        println!(
+            "#{} {} bids {} CKB at {}",
+            i + 1,
+            item.bid.bidder,
+            capacity::format_ckb(item.bid.price),
+            item.bid.timestamp
+        );
+        if let Some(err) = &item.error {
+            println!("  ⚠️ {}", err);
+            continue;
+        }
+
+        println!("  ends at {}", item.ends_at);
+        if let Some((bidder, price)) = &item.refund {
+            println!("  {:<20} {:>20} CKB refunded", bidder, capacity::format_ckb(*price));
+        }
+        for share in item.shares.iter() {
+            println!(
+                "  {:<20} {:>20} CKB",
+                share.party.to_string(),
+                capacity::format_ckb(share.amount)
+            );
+        }
+    }
+
+    println!();
+    match &result.winner {
+        Some((bidder, price)) => println!(
+            "Winner: {} with {} CKB, the auction ends at {}",
+            bidder,
+            capacity::format_ckb(*price),
+            result.ends_at
+        ),
+        None => println!("No valid bid, the auction ends at {}", result.ends_at),
+    }
+    for party in [
+        profit_rate::Party::PrevBidder,
+        profit_rate::Party::Inviter,
+        profit_rate::Party::Channel,
+        profit_rate::Party::Das,
+        profit_rate::Party::Seller,
+    ] {
+        println!(
+            "  {:<20} {:>20} CKB",
+            party.to_string(),
+            capacity::format_ckb(result.total_of(party))
+        );
+    }
+}
+
+fn check_market_order(options: CheckMarketOrderOptions) {
+    let network = match options.network {
+        Some(network) => network.parse::<Network>().unwrap_or_else(|err| panic!("{}", err)),
+        None => Network::current(),
+    };
+    let config = secondary_market::secondary_market_config_of(network);
+    let order = secondary_market::load_market_order(Path::new(&options.order));
+
+    let errors = secondary_market::check_market_order(&config, &order);
+    if errors.is_empty() {
+        println!("The order is valid on {} ✅", network);
+        return;
+    }
+
+    println!("The order breaks {} limits on {}:", errors.len(), network);
+    for err in errors.iter() {
+        println!("⚠️ {}", err);
+    }
+    std::process::exit(1);
+}
+
+/// Parse the time of a flag which is a unix timestamp or a datetime in UTC, the error names the flag and the formats.
+fn parse_time(flag: &str, time: &str) -> u64 {
+    time.parse::<u64>()
+        .ok()
+        .or_else(|| util::parse_timestamp(time))
+        .unwrap_or_else(|| {
+            panic!(
+                "--{}={} is invalid, it should be like \"2022-01-01 00:00:00\" in UTC or a unix timestamp.",
+                flag, time
+            )
+        })
+}
+
+fn calc_expiration(options: ExpirationOptions) {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let mut expiration = match (options.expired_at, options.registered_at) {
+        (Some(expired_at), _) => expiration::Expiration::new(parse_time("expired-at", &expired_at)),
+        (None, Some(registered_at)) => {
+            let registered_at = parse_time("registered-at", &registered_at);
+            println!("Registered at: {}", util::format_timestamp(registered_at));
+            expiration::Expiration::from_registration(registered_at, options.years)
+        }
+        (None, None) => panic!("One of --expired-at and --registered-at is required."),
+    };
+
+    let print_expiration = |expiration: &expiration::Expiration| {
+        println!(
+            "  Expired at:             {} ({})",
+            util::format_timestamp(expiration.expired_at),
+            expiration.expired_at
+        );
+        println!(
+            "  Grace period ends at:   {} ({})",
+            util::format_timestamp(expiration.grace_period_ends_at),
+            expiration.grace_period_ends_at
+        );
+        println!(
+            "  Re-registrable at:      {} ({})",
+            util::format_timestamp(expiration.re_registrable_at),
+            expiration.re_registrable_at
+        );
+    };
+    print_expiration(&expiration);
+
+    if let Some(years) = options.renew_years {
+        let renewed_at = options
+            .renewed_at
+            .map(|time| parse_time("renewed-at", &time))
+            .unwrap_or(now);
+        expiration = match expiration.renew(years, renewed_at) {
+            Ok(expiration) => expiration,
+            Err(err) => {
+                println!("⚠️ {}", err);
+                std::process::exit(1);
+            }
+        };
+        println!("Renewed for {} years at {}:", years, util::format_timestamp(renewed_at));
+        print_expiration(&expiration);
+    }
+
+    let as_of = options.as_of.map(|time| parse_time("as-of", &time)).unwrap_or(now);
+    println!(
+        "Status at {}: {:?}",
+        util::format_timestamp(as_of),
+        expiration.status_at(as_of)
+    );
+}
+
+fn consolidate_income(options: ConsolidateIncomeOptions) {
+    let config = income::INCOME_CONFIG;
+    let cells = income::load_income_cells(Path::new(&options.cells));
+    let rate = match options.rates {
+        Some(path) => profit_rate::ProfitRate::load(Path::new(&path)),
+        None => profit_rate::PROFIT_RATE,
+    };
+
+    let errors = income::check_income_cells(&config, &cells);
+    for err in errors.iter() {
+        println!("⚠️ {}", err);
+    }
+    if !errors.is_empty() {
+        std::process::exit(1);
+    }
+
+    let plan = income::plan_consolidation(&config, rate.income_consolidate, &options.consolidator, &cells);
+    println!(
+        "Inputs: {} IncomeCells with {} records",
+        cells.len(),
+        cells.iter().map(|cell| cell.records.len()).sum::<usize>()
+    );
+
+    println!("Transferred to owners: {}", plan.transfers.len());
+    for item in plan.transfers.iter() {
+        println!(
+            "  {} {} CKB, fee {} CKB",
+            item.lock,
+            capacity::format_ckb(item.capacity),
+            capacity::format_ckb(item.fee)
+        );
+    }
+
+    println!("Kept in IncomeCells: {}", plan.income_cells.len());
+    for (i, records) in plan.income_cells.iter().enumerate() {
+        println!(
+            "  IncomeCell #{}: {} records, {} CKB",
+            i + 1,
+            records.len(),
+            capacity::format_ckb(records.iter().map(|record| record.capacity).sum())
+        );
+        for record in records.iter() {
+            println!("    {} {} CKB", record.lock, capacity::format_ckb(record.capacity));
+        }
+    }
+
+    println!(
+        "Consolidator fee: {} CKB, padding: {} CKB",
+        capacity::format_ckb(plan.fee()),
+        capacity::format_ckb(plan.padding)
+    );
+    println!("Outputs: {}, plus the change of the consolidator", plan.output_count());
+}
+
+///0x6d000000
+fn gen_config_cell_release() -> String {
+    // Plan the rate with `cargo run --bin=account-cell-type-generator -- release`.
+    let lucky_number = release::lucky_number_of(release::release_rate_of(Network::current()));
 
     let entity = ConfigCellRelease::new_builder()
         .lucky_number(Uint32::from(lucky_number))
@@ -378,27 +918,29 @@ fn gen_config_cell_release() -> String {
 
 fn gen_config_cell_secondary_market() -> String {
     // CAREFUL The minimum price should contains the basic_capacity of AccountCell.
+    let market = secondary_market::secondary_market_config_of(Network::current());
+    let auction = auction::AUCTION_CONFIG;
     let entity = ConfigCellSecondaryMarket::new_builder()
-        .common_fee(Uint64::from(10_000))
+        .common_fee(Uint64::from(market.common_fee))
         // sale
-        .sale_min_price(Uint64::from(20_000_000_000))
-        .sale_expiration_limit(Uint32::from(86400 * 30))
-        .sale_description_bytes_limit(Uint32::from(5000))
-        .sale_cell_basic_capacity(Uint64::from(20_000_000_000))
-        .sale_cell_prepared_fee_capacity(Uint64::from(100_000_000))
+        .sale_min_price(Uint64::from(market.sale_min_price))
+        .sale_expiration_limit(Uint32::from(market.sale_expiration_limit as u32))
+        .sale_description_bytes_limit(Uint32::from(market.sale_description_bytes_limit as u32))
+        .sale_cell_basic_capacity(Uint64::from(capacity::SALE_CELL_BASIC_CAPACITY))
+        .sale_cell_prepared_fee_capacity(Uint64::from(capacity::SALE_CELL_PREPARED_FEE_CAPACITY))
         // offser
-        .offer_cell_basic_capacity(Uint64::from(20_000_000_000))
-        .offer_cell_prepared_fee_capacity(Uint64::from(100_000_000))
-        .offer_min_price(Uint64::from(100_000_000_000))
-        .offer_message_bytes_limit(Uint32::from(5000))
+        .offer_cell_basic_capacity(Uint64::from(capacity::OFFER_CELL_BASIC_CAPACITY))
+        .offer_cell_prepared_fee_capacity(Uint64::from(capacity::OFFER_CELL_PREPARED_FEE_CAPACITY))
+        .offer_min_price(Uint64::from(market.offer_min_price))
+        .offer_message_bytes_limit(Uint32::from(market.offer_message_bytes_limit as u32))
         // auction
-        .auction_max_extendable_duration(Uint32::from(86400 * 7))
-        .auction_duration_increment_each_bid(Uint32::from(600))
-        .auction_min_opening_price(Uint64::from(200_000_000_000))
-        .auction_min_increment_rate_each_bid(Uint32::from(1000))
+        .auction_max_extendable_duration(Uint32::from(auction.max_extendable_duration as u32))
+        .auction_duration_increment_each_bid(Uint32::from(auction.duration_increment_each_bid as u32))
+        .auction_min_opening_price(Uint64::from(auction.min_opening_price))
+        .auction_min_increment_rate_each_bid(Uint32::from(auction.min_increment_rate_each_bid))
         .auction_description_bytes_limit(Uint32::from(5000))
-        .auction_cell_basic_capacity(Uint64::from(20_000_000_000))
-        .auction_cell_prepared_fee_capacity(Uint64::from(100_000_000))
+        .auction_cell_basic_capacity(Uint64::from(capacity::AUCTION_CELL_BASIC_CAPACITY))
+        .auction_cell_prepared_fee_capacity(Uint64::from(capacity::AUCTION_CELL_PREPARED_FEE_CAPACITY))
         .build();
 
     gen_return_from_entity!(DataType::ConfigCellSecondaryMarket, entity)
@@ -406,8 +948,8 @@ fn gen_config_cell_secondary_market() -> String {
 
 fn gen_config_cell_reverse_resolution() -> String {
     let entity = ConfigCellReverseResolution::new_builder()
-        .record_basic_capacity(Uint64::from(20_000_000_000))
-        .record_prepared_fee_capacity(Uint64::from(100_000_000))
+        .record_basic_capacity(Uint64::from(capacity::REVERSE_RECORD_BASIC_CAPACITY))
+        .record_prepared_fee_capacity(Uint64::from(capacity::REVERSE_RECORD_PREPARED_FEE_CAPACITY))
         .common_fee(Uint64::from(10_000))
         .build();
 
@@ -416,8 +958,8 @@ fn gen_config_cell_reverse_resolution() -> String {
 
 fn gen_config_cell_sub_account() -> String {
     let entity = ConfigCellSubAccount::new_builder()
-        .basic_capacity(Uint64::from(20_000_000_000))
-        .prepared_fee_capacity(Uint64::from(2_000_000_000))
+        .basic_capacity(Uint64::from(capacity::SUB_ACCOUNT_CELL_BASIC_CAPACITY))
+        .prepared_fee_capacity(Uint64::from(capacity::SUB_ACCOUNT_CELL_PREPARED_FEE_CAPACITY))
         .new_sub_account_price(Uint64::from(100_000_000))
         .renew_sub_account_price(Uint64::from(100_000_000))
         .new_sub_account_custom_price_das_profit_rate(Uint32::from(300))
@@ -534,7 +1076,178 @@ fn gen_config_cell_system_status() -> String {
     gen_return_from_entity!(DataType::ConfigCellSystemStatus, entity)
 }
 
+#[derive(Clap, Debug)]
+#[clap(version = "0.2.1", author = "Link Xie. <xieaolin@gmail.com>")]
+struct Options {
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Clap, Debug)]
+enum Command {
+    #[clap(about = "Report the problems of char set files, like duplicated characters and not fully-qualified emoji.")]
+    CheckCharSets,
+    #[clap(about = "Report the visually confusable characters across char sets and the look-alikes of accounts.")]
+    CheckConfusables(CheckConfusablesOptions),
+    #[clap(about = "Generate char set files from the recipes and the Unicode data in ./data/unicode .")]
+    GenCharSets(GenCharSetsOptions),
+    #[clap(about = "Export the enabled char sets and the hashes of their ConfigCell witnesses as a JSON bundle.")]
+    ExportCharSets(ExportCharSetsOptions),
+    #[clap(about = "Check the record keys and report the keys added or removed since the previous release.")]
+    CheckRecordKeys(CheckRecordKeysOptions),
+    #[clap(about = "Generate the address.* record keys from the coin type registry in ./data/coin_type_registry.toml .")]
+    GenAddressKeys(GenAddressKeysOptions),
+    #[clap(about = "Check a JSON list of records against the ConfigCells and print the Records molecule.")]
+    CheckRecords(CheckRecordsOptions),
+    #[clap(about = "Split the amount of a registration, sale or auction to parties with ConfigCellProfitRate.")]
+    Profit(ProfitOptions),
+    #[clap(about = "Check that the configured basic capacities cover the occupied capacities of cells.")]
+    CheckCapacities(CheckCapacitiesOptions),
+    #[clap(about = "Replay a scripted auction with ConfigCellSecondaryMarket and ConfigCellProfitRate.")]
+    SimulateAuction(SimulateAuctionOptions),
+    #[clap(about = "Check a JSON sale or offer against the limits of ConfigCellSecondaryMarket.")]
+    CheckMarketOrder(CheckMarketOrderOptions),
+    #[clap(about = "Calculate the expiration, the end of grace period and the re-registration time of an account.")]
+    Expiration(ExpirationOptions),
+    #[clap(about = "Plan the consolidation of IncomeCells with ConfigCellIncome and ConfigCellProfitRate.")]
+    ConsolidateIncome(ConsolidateIncomeOptions),
+}
+
+#[derive(Clap, Debug)]
+struct CheckConfusablesOptions {
+    #[clap(long = "all", about = "Include the disabled char sets.")]
+    all: bool,
+    #[clap(long = "account", about = "Account to check whether it has look-alikes in other char sets.")]
+    accounts: Vec<String>,
+    #[clap(long = "reserved", about = "Check all the reserved accounts in ./data/reserved_accounts.txt.")]
+    reserved: bool,
+}
+
+#[derive(Clap, Debug)]
+struct GenCharSetsOptions {
+    #[clap(long = "name", about = "Name of the char set in the registry to generate, all by default.")]
+    names: Vec<String>,
+    #[clap(long = "check", about = "Only report the char set files which differ from their recipes.")]
+    check: bool,
+}
+
+#[derive(Clap, Debug)]
+struct ExportCharSetsOptions {
+    #[clap(long = "output", about = "The file to write the bundle to, print to stdout by default.")]
+    output: Option<String>,
+}
+
+#[derive(Clap, Debug)]
+struct CheckRecordKeysOptions {
+    #[clap(long = "previous", about = "The record_key_namespace.txt of the previous release.")]
+    previous: Option<String>,
+}
+
+#[derive(Clap, Debug)]
+struct GenAddressKeysOptions {
+    #[clap(long = "check", about = "Only report the address.* keys which do not match the coin type registry.")]
+    check: bool,
+}
+
+#[derive(Clap, Debug)]
+struct CheckRecordsOptions {
+    #[clap(long = "records", about = "The JSON file of records, every record has type, key, label, value and ttl.")]
+    records: String,
+}
+
+#[derive(Clap, Debug)]
+struct ProfitOptions {
+    #[clap(long = "event", about = "The event which makes the profit, registration, sale or auction.")]
+    event: String,
+    #[clap(long = "amount", about = "The profit of registration or the price of sale and auction in shannons.")]
+    amount: u64,
+    #[clap(
+        long = "previous-price",
+        about = "The price of the previous bid of an auction in shannons, omit it for the first bid."
+    )]
+    previous_price: Option<u64>,
+    #[clap(long = "no-inviter", about = "There is no inviter, the share of the inviter goes to DAS.")]
+    no_inviter: bool,
+    #[clap(long = "no-channel", about = "There is no channel, the share of the channel goes to DAS.")]
+    no_channel: bool,
+    #[clap(long = "rates", about = "A TOML file of proposed rates, the rates of ConfigCellProfitRate by default.")]
+    rates: Option<String>,
+}
+
+#[derive(Clap, Debug)]
+struct CheckCapacitiesOptions {
+    #[clap(long = "account-size", default_value = "46", about = "The bytes of an account with .bit in ReverseRecordCell.")]
+    account_size: usize,
+}
+
+#[derive(Clap, Debug)]
+struct SimulateAuctionOptions {
+    #[clap(long = "script", about = "A TOML file of the opening price, start time, duration and bids.")]
+    script: String,
+    #[clap(long = "rates", about = "A TOML file of proposed rates, the rates of ConfigCellProfitRate by default.")]
+    rates: Option<String>,
+}
+
+#[derive(Clap, Debug)]
+struct CheckMarketOrderOptions {
+    #[clap(long = "order", about = "The JSON file of a sale or an offer.")]
+    order: String,
+    #[clap(long = "network", about = "The network of limits, mainnet, testnet or dev.")]
+    network: Option<String>,
+}
+
+#[derive(Clap, Debug)]
+struct ExpirationOptions {
+    #[clap(long = "registered-at", about = "The registration time, like 2022-01-01 00:00:00 or a unix timestamp.")]
+    registered_at: Option<String>,
+    #[clap(long = "years", default_value = "1", about = "The years of registration.")]
+    years: u64,
+    #[clap(
+        long = "expired-at",
+        conflicts_with = "registered-at",
+        about = "The expired_at of AccountCell instead of --registered-at and --years."
+    )]
+    expired_at: Option<String>,
+    #[clap(long = "renew-years", about = "The years to renew the account.")]
+    renew_years: Option<u64>,
+    #[clap(long = "renewed-at", about = "The time of renewal, now by default.")]
+    renewed_at: Option<String>,
+    #[clap(long = "as-of", about = "The time to report the status of the account at, now by default.")]
+    as_of: Option<String>,
+}
+
+#[derive(Clap, Debug)]
+struct ConsolidateIncomeOptions {
+    #[clap(long = "cells", about = "The JSON file of IncomeCells with records of lock and capacity.")]
+    cells: String,
+    #[clap(long = "consolidator", about = "The lock of the consolidator in hex.")]
+    consolidator: String,
+    #[clap(long = "rates", about = "A TOML file of proposed rates, the rates of ConfigCellProfitRate by default.")]
+    rates: Option<String>,
+}
+
 fn main() {
+    let options: Options = Options::parse();
+
+    match options.command {
+        Some(Command::CheckCharSets) => check_char_sets(),
+        Some(Command::CheckConfusables(options)) => check_confusables(options),
+        Some(Command::GenCharSets(options)) => gen_char_sets(options),
+        Some(Command::ExportCharSets(options)) => export_char_sets(options),
+        Some(Command::CheckRecordKeys(options)) => check_record_keys(options),
+        Some(Command::GenAddressKeys(options)) => gen_address_keys(options),
+        Some(Command::CheckRecords(options)) => check_records(options),
+        Some(Command::Profit(options)) => calc_profit(options),
+        Some(Command::CheckCapacities(options)) => check_capacities(options),
+        Some(Command::SimulateAuction(options)) => simulate_auction(options),
+        Some(Command::CheckMarketOrder(options)) => check_market_order(options),
+        Some(Command::Expiration(options)) => calc_expiration(options),
+        Some(Command::ConsolidateIncome(options)) => consolidate_income(options),
+        None => gen_config_cells(),
+    }
+}
+
+fn gen_config_cells() {
     print!("{},", gen_config_cell_account());
     print!("{},", gen_config_cell_apply());
     print!("{},", gen_config_cell_income());
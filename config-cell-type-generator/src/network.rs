@@ -0,0 +1,44 @@
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Dev,
+}
+
+impl Network {
+    /// The network which the generator is built for, it is decided by features.
+    pub fn current() -> Self {
+        if cfg!(feature = "mainnet") {
+            Network::Mainnet
+        } else if cfg!(any(feature = "testnet2", feature = "testnet3")) {
+            Network::Testnet
+        } else {
+            Network::Dev
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mainnet" => Ok(Network::Mainnet),
+            "testnet" => Ok(Network::Testnet),
+            "dev" => Ok(Network::Dev),
+            _ => Err(format!("Network {} is unknown, it should be mainnet, testnet or dev.", s)),
+        }
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Network::Mainnet => write!(f, "mainnet"),
+            Network::Testnet => write!(f, "testnet"),
+            Network::Dev => write!(f, "dev"),
+        }
+    }
+}
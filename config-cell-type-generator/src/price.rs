@@ -0,0 +1,84 @@
+use super::network::Network;
+
+/// The base of rates like discounts, 10_000 means 100%.
+pub const RATE_BASE: u64 = 10_000;
+pub const ONE_CKB: u64 = 100_000_000;
+/// The unit of prices and quotes, prices are in USD with 6 decimals.
+pub const ONE_USD: u64 = 1_000_000;
+pub const INVITED_DISCOUNT: u32 = 500;
+
+/// The yearly prices of accounts in USD with 6 decimals, the price of the largest length also applies to the longer
+/// accounts.
+#[derive(Debug, Clone, Copy)]
+pub struct Price {
+    pub length: u8,
+    pub new: u64,
+    pub renew: u64,
+}
+
+impl Price {
+    const fn new(length: u8, new: u64, renew: u64) -> Self {
+        Price { length, new, renew }
+    }
+}
+
+const MAINNET_PRICES: [Price; 8] = [
+    Price::new(1, 1_000_000, 1_000_000),
+    Price::new(2, 1_024_000_000, 1_024_000_000),
+    Price::new(3, 660_000_000, 660_000_000),
+    Price::new(4, 160_000_000, 160_000_000),
+    Price::new(5, 5_000_000, 5_000_000),
+    Price::new(6, 5_000_000, 5_000_000),
+    Price::new(7, 5_000_000, 5_000_000),
+    Price::new(8, 5_000_000, 5_000_000),
+];
+
+const TESTNET_PRICES: [Price; 8] = [
+    Price::new(1, 1_000_000, 1_000_000),
+    Price::new(2, 30_000_000, 30_000_000),
+    Price::new(3, 20_000_000, 20_000_000),
+    Price::new(4, 10_000_000, 10_000_000),
+    Price::new(5, 5_000_000, 5_000_000),
+    Price::new(6, 5_000_000, 5_000_000),
+    Price::new(7, 5_000_000, 5_000_000),
+    Price::new(8, 5_000_000, 5_000_000),
+];
+
+pub fn prices_of(network: Network) -> &'static [Price] {
+    match network {
+        Network::Mainnet => &MAINNET_PRICES,
+        Network::Testnet | Network::Dev => &TESTNET_PRICES,
+    }
+}
+
+/// Find the price of an account length in the same way as the contracts, the last price is used when there is no
+/// price for the length.
+pub fn price_of_length(prices: &[Price], length: usize) -> Price {
+    prices
+        .iter()
+        .find(|price| price.length as usize == length)
+        .unwrap_or_else(|| prices.last().unwrap())
+        .to_owned()
+}
+
+/// Convert a yearly price in USD to CKB shannons with the quote and discount, the integer rounding is the same as the
+/// contracts.
+pub fn calc_yearly_capacity(yearly_price: u64, quote: u64, discount: u32) -> u64 {
+    let total = if yearly_price < quote {
+        yearly_price * ONE_CKB / quote
+    } else {
+        yearly_price / quote * ONE_CKB
+    };
+
+    total - (total * discount as u64 / RATE_BASE)
+}
+
+/// Apply a discount to a price in USD with the same rounding as calc_yearly_capacity.
+pub fn discount_price(price: u64, discount: u32) -> u64 {
+    price - (price * discount as u64 / RATE_BASE)
+}
+
+/// Format an amount in USD with 6 decimals, like 5.000000 .
+pub fn format_usd(amount: u64) -> String {
+    format!("{}.{:06}", amount / ONE_USD, amount % ONE_USD)
+}
@@ -0,0 +1,322 @@
+use serde::Deserialize;
+use std::{fmt, fs, path::Path, str::FromStr};
+
+use super::price::RATE_BASE;
+
+/// The rates of profits in ConfigCellProfitRate, they are all based on RATE_BASE.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfitRate {
+    pub channel: u32,
+    pub inviter: u32,
+    pub proposal_create: u32,
+    pub proposal_confirm: u32,
+    pub income_consolidate: u32,
+    pub sale_buyer_inviter: u32,
+    pub sale_buyer_channel: u32,
+    pub sale_das: u32,
+    pub auction_bidder_inviter: u32,
+    pub auction_bidder_channel: u32,
+    pub auction_das: u32,
+    pub auction_prev_bidder: u32,
+}
+
+pub const PROFIT_RATE: ProfitRate = ProfitRate {
+    channel: 1000,
+    inviter: 1000,
+    proposal_create: 200,
+    proposal_confirm: 0,
+    income_consolidate: 500,
+    sale_buyer_inviter: 100,
+    sale_buyer_channel: 150,
+    sale_das: 100,
+    auction_bidder_inviter: 100,
+    auction_bidder_channel: 100,
+    auction_das: 100,
+    auction_prev_bidder: 4700,
+};
+
+impl ProfitRate {
+    /// Load proposed rates from a TOML file with the same fields as ConfigCellProfitRate.
+    pub fn load(path: &Path) -> Self {
+        let data = fs::read_to_string(path).expect(format!("Expect file {} exist.", path.display()).as_str());
+        toml::from_str(&data).expect(format!("Expect file {} to be valid.", path.display()).as_str())
+    }
+
+    /// Check that the rates shared in every event are not more than 100% in total.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for event in [ProfitEvent::Registration, ProfitEvent::Sale, ProfitEvent::Auction] {
+            let total = self.rates_of(event).iter().map(|(_, rate)| *rate as u64).sum::<u64>();
+            if total > RATE_BASE {
+                errors.push(format!(
+                    "The rates of {} are {} in total, it should not be more than {}.",
+                    event, total, RATE_BASE
+                ));
+            }
+        }
+
+        errors
+    }
+
+    /// The parties which share the profit of an event with their rates, the remainder goes to the remainder party
+    /// of the event.
+    pub fn rates_of(&self, event: ProfitEvent) -> Vec<(Party, u32)> {
+        match event {
+            ProfitEvent::Registration => vec![
+                (Party::Inviter, self.inviter),
+                (Party::Channel, self.channel),
+                (Party::ProposalCreator, self.proposal_create),
+                (Party::ProposalConfirmer, self.proposal_confirm),
+            ],
+            ProfitEvent::Sale => vec![
+                (Party::Inviter, self.sale_buyer_inviter),
+                (Party::Channel, self.sale_buyer_channel),
+                (Party::Das, self.sale_das),
+            ],
+            ProfitEvent::Auction => vec![
+                (Party::Inviter, self.auction_bidder_inviter),
+                (Party::Channel, self.auction_bidder_channel),
+                (Party::Das, self.auction_das),
+                (Party::PrevBidder, self.auction_prev_bidder),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfitEvent {
+    Registration,
+    Sale,
+    Auction,
+}
+
+impl ProfitEvent {
+    /// The party who receives the remainder after all the shares are paid, including the dust of rounding.
+    pub fn remainder_party(&self) -> Party {
+        match self {
+            ProfitEvent::Registration => Party::Das,
+            ProfitEvent::Sale | ProfitEvent::Auction => Party::Seller,
+        }
+    }
+}
+
+impl FromStr for ProfitEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "registration" => Ok(ProfitEvent::Registration),
+            "sale" => Ok(ProfitEvent::Sale),
+            "auction" => Ok(ProfitEvent::Auction),
+            _ => Err(format!(
+                "Event {} is unknown, it should be registration, sale or auction.",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ProfitEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProfitEvent::Registration => write!(f, "registration"),
+            ProfitEvent::Sale => write!(f, "sale"),
+            ProfitEvent::Auction => write!(f, "auction"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Party {
+    /// The inviter of the owner in registration, or the inviter of the buyer or bidder.
+    Inviter,
+    /// The channel of the owner in registration, or the channel of the buyer or bidder.
+    Channel,
+    ProposalCreator,
+    ProposalConfirmer,
+    PrevBidder,
+    Seller,
+    Das,
+}
+
+impl fmt::Display for Party {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Party::Inviter => write!(f, "inviter"),
+            Party::Channel => write!(f, "channel"),
+            Party::ProposalCreator => write!(f, "proposal creator"),
+            Party::ProposalConfirmer => write!(f, "proposal confirmer"),
+            Party::PrevBidder => write!(f, "previous bidder"),
+            Party::Seller => write!(f, "seller"),
+            Party::Das => write!(f, "DAS"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProfitShare {
+    pub party: Party,
+    /// The rate of the share, None for the remainder.
+    pub rate: Option<u32>,
+    pub amount: u64,
+}
+
+/// Split the amount of an event into shares in shannons with the same rounding as the contracts, every share is
+/// rounded down and the remainder goes to the remainder party of the event. The shares of the inviter and channel go
+/// to DAS when there is no inviter or channel.
+pub fn distribute_profit(
+    rate: &ProfitRate,
+    event: ProfitEvent,
+    amount: u64,
+    has_inviter: bool,
+    has_channel: bool,
+) -> Vec<ProfitShare> {
+    let mut shares: Vec<ProfitShare> = Vec::new();
+    let mut paid = 0u64;
+    for (party, party_rate) in rate.rates_of(event) {
+        // The product of an amount and a rate may overflow u64, but the share itself never does.
+        let share = (amount as u128 * party_rate as u128 / RATE_BASE as u128) as u64;
+        let party = match party {
+            Party::Inviter if !has_inviter => Party::Das,
+            Party::Channel if !has_channel => Party::Das,
+            _ => party,
+        };
+        paid += share;
+
+        match shares.iter_mut().find(|item| item.party == party) {
+            Some(item) => {
+                item.rate = item.rate.map(|rate| rate + party_rate);
+                item.amount += share;
+            }
+            None => shares.push(ProfitShare {
+                party,
+                rate: Some(party_rate),
+                amount: share,
+            }),
+        }
+    }
+
+    let remainder = amount - paid;
+    let party = event.remainder_party();
+    match shares.iter_mut().find(|item| item.party == party) {
+        Some(item) => {
+            item.rate = None;
+            item.amount += remainder;
+        }
+        None => shares.push(ProfitShare {
+            party,
+            rate: None,
+            amount: remainder,
+        }),
+    }
+
+    shares
+}
+
+/// Split a bid of an auction with the same rules as the contracts, the previous bidder is refunded besides the shares.
+///
+/// The increment of the price over the previous price is split by the auction rates with the remainder to the seller.
+/// There is no previous bidder for the first bid, so the whole price is split and the share of the previous bidder
+/// goes to the seller too.
+pub fn distribute_bid(
+    rate: &ProfitRate,
+    previous_price: Option<u64>,
+    price: u64,
+    has_inviter: bool,
+    has_channel: bool,
+) -> Vec<ProfitShare> {
+    match previous_price {
+        Some(previous_price) => {
+            let increment = price
+                .checked_sub(previous_price)
+                .expect(format!("The price {} is less than the previous {}.", price, previous_price).as_str());
+            distribute_profit(rate, ProfitEvent::Auction, increment, has_inviter, has_channel)
+        }
+        None => {
+            let rate = ProfitRate {
+                auction_prev_bidder: 0,
+                ..rate.clone()
+            };
+            distribute_profit(&rate, ProfitEvent::Auction, price, has_inviter, has_channel)
+                .into_iter()
+                .filter(|share| share.party != Party::PrevBidder)
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distribute_profit_of_registration() {
+        let shares = distribute_profit(&PROFIT_RATE, ProfitEvent::Registration, 10_000_000_001, true, false)
+            .into_iter()
+            .map(|share| (share.party, share.amount))
+            .collect::<Vec<_>>();
+
+        // The share of the missing channel and the dust of rounding go to DAS.
+        assert_eq!(
+            shares,
+            vec![
+                (Party::Inviter, 1_000_000_000),
+                (Party::Das, 8_800_000_001),
+                (Party::ProposalCreator, 200_000_000),
+                (Party::ProposalConfirmer, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_distribute_profit_without_overflow() {
+        let shares = distribute_profit(&PROFIT_RATE, ProfitEvent::Sale, u64::MAX, true, true)
+            .into_iter()
+            .map(|share| (share.party, share.amount))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            shares,
+            vec![
+                (Party::Inviter, 184_467_440_737_095_516),
+                (Party::Channel, 276_701_161_105_643_274),
+                (Party::Das, 184_467_440_737_095_516),
+                (Party::Seller, 17_801_108_031_129_717_309),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_distribute_bid() {
+        let shares = distribute_bid(&PROFIT_RATE, Some(200_000_000_000), 220_000_000_000, true, true)
+            .into_iter()
+            .map(|share| (share.party, share.amount))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            shares,
+            vec![
+                (Party::Inviter, 200_000_000),
+                (Party::Channel, 200_000_000),
+                (Party::Das, 200_000_000),
+                (Party::PrevBidder, 9_400_000_000),
+                (Party::Seller, 10_000_000_000),
+            ]
+        );
+
+        // The share of the previous bidder goes to the seller for the first bid.
+        let shares = distribute_bid(&PROFIT_RATE, None, 200_000_000_000, true, true)
+            .into_iter()
+            .map(|share| (share.party, share.amount))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            shares,
+            vec![
+                (Party::Inviter, 2_000_000_000),
+                (Party::Channel, 2_000_000_000),
+                (Party::Das, 2_000_000_000),
+                (Party::Seller, 194_000_000_000),
+            ]
+        );
+    }
+}
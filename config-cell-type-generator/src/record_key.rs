@@ -0,0 +1,103 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    path::Path,
+};
+
+use super::constants::*;
+use super::util::read_lines_in;
+
+pub const RECORD_KEY_NAMESPACE_FILE: &str = "record_key_namespace.txt";
+
+pub struct RecordKeys {
+    /// The valid keys in their original order.
+    pub keys: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl RecordKeys {
+    pub fn count_by_namespace(&self) -> BTreeMap<&str, usize> {
+        let mut ret = BTreeMap::new();
+        for key in self.keys.iter() {
+            *ret.entry(key.split('.').next().unwrap()).or_insert(0) += 1;
+        }
+
+        ret
+    }
+}
+
+/// Check a record key is in the form of `namespace.key`, the namespace must be known and the key may only contain
+/// lowercase letters, digits and underscores.
+pub fn check_record_key(key: &str) -> Result<(), String> {
+    if key.len() > RECORD_KEY_MAX_LENGTH {
+        return Err(format!(
+            "The key {} is longer than {} bytes.",
+            key, RECORD_KEY_MAX_LENGTH
+        ));
+    }
+
+    let (namespace, name) = match key.split_once('.') {
+        Some(item) => item,
+        None => return Err(format!("The key {} should be in the form of namespace.key .", key)),
+    };
+    if !RECORD_KEY_NAMESPACES.contains(&namespace) {
+        return Err(format!(
+            "The namespace of key {} is unknown, it should be one of {}.",
+            key,
+            RECORD_KEY_NAMESPACES.join(", ")
+        ));
+    }
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|char| char.is_ascii_lowercase() || char.is_ascii_digit() || char == '_')
+    {
+        return Err(format!(
+            "The key {} should only contain lowercase letters, digits and underscores after the namespace.",
+            key
+        ));
+    }
+
+    Ok(())
+}
+
+/// Load record keys from a file and check them, the invalid, duplicated keys and empty lines are reported as
+/// errors with their line numbers.
+pub fn load_record_keys(data_dir: &Path, file: &str) -> RecordKeys {
+    let path = data_dir.join(file);
+    let lines = read_lines_in(data_dir, file).expect(format!("Expect file {} exist.", path.display()).as_str());
+
+    let mut keys = Vec::new();
+    let mut errors = Vec::new();
+    let mut dedup_keys = HashSet::new();
+    for (i, line) in lines.enumerate() {
+        if let Ok(key) = line {
+            if key.trim().is_empty() {
+                errors.push(format!("{} line {}: The line is empty.", file, i + 1));
+                continue;
+            }
+            if let Err(err) = check_record_key(&key) {
+                errors.push(format!("{} line {}: {}", file, i + 1, err));
+                continue;
+            }
+            if !dedup_keys.insert(key.clone()) {
+                errors.push(format!("{} line {}: The key {} is duplicated.", file, i + 1, key));
+                continue;
+            }
+
+            keys.push(key);
+        }
+    }
+
+    RecordKeys { keys, errors }
+}
+
+/// Compare the keys with the ones of the previous release, return the added and removed keys.
+pub fn diff_record_keys(current: &[String], previous: &[String]) -> (Vec<String>, Vec<String>) {
+    let current = current.iter().collect::<BTreeSet<_>>();
+    let previous = previous.iter().collect::<BTreeSet<_>>();
+
+    let added = current.difference(&previous).map(|key| key.to_string()).collect();
+    let removed = previous.difference(&current).map(|key| key.to_string()).collect();
+
+    (added, removed)
+}
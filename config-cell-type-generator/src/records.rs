@@ -0,0 +1,86 @@
+use das_types_std::{packed::*, prelude::*};
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+use super::constants::*;
+use super::record_key::check_record_key;
+
+/// The namespace whose keys are defined by users, so they are not listed in record_key_namespace.txt .
+pub const CUSTOM_KEY_NAMESPACE: &str = "custom_key";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RecordItem {
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub key: String,
+    #[serde(default)]
+    pub label: String,
+    pub value: String,
+    pub ttl: u32,
+}
+
+impl RecordItem {
+    /// The key in record_key_namespace.txt, like address.eth .
+    pub fn namespace_key(&self) -> String {
+        format!("{}.{}", self.record_type, self.key)
+    }
+}
+
+/// Load records from a JSON file like `[{"type": "address", "key": "eth", "label": "", "value": "0x...", "ttl": 300}]`.
+pub fn load_records(path: &Path) -> Vec<RecordItem> {
+    let data = fs::read_to_string(path).expect(format!("Expect file {} exist.", path.display()).as_str());
+    serde_json::from_str(&data)
+        .expect(format!("Expect file {} to be a valid list of records.", path.display()).as_str())
+}
+
+pub fn gen_records(records: &[RecordItem]) -> Records {
+    let mut builder = Records::new_builder();
+    for record in records {
+        builder = builder.push(
+            Record::new_builder()
+                .record_type(Bytes::from(record.record_type.as_bytes().to_vec()))
+                .record_key(Bytes::from(record.key.as_bytes().to_vec()))
+                .record_label(Bytes::from(record.label.as_bytes().to_vec()))
+                .record_value(Bytes::from(record.value.as_bytes().to_vec()))
+                .record_ttl(Uint32::from(record.ttl))
+                .build(),
+        );
+    }
+
+    builder.build()
+}
+
+/// Check records against the record key namespace and the record_min_ttl and record_size_limit of ConfigCellAccount.
+pub fn check_records(records: &[RecordItem], namespace: &[String]) -> Vec<String> {
+    let mut errors = Vec::new();
+    for (i, record) in records.iter().enumerate() {
+        let key = record.namespace_key();
+        if record.record_type == CUSTOM_KEY_NAMESPACE {
+            if let Err(err) = check_record_key(&key) {
+                errors.push(format!("Record {}: {}", i, err));
+            }
+        } else if !namespace.contains(&key) {
+            errors.push(format!(
+                "Record {}: The key {} is not in the record key namespace.",
+                i, key
+            ));
+        }
+
+        if record.ttl < RECORD_MIN_TTL {
+            errors.push(format!(
+                "Record {}: The ttl {} is less than the record_min_ttl {}.",
+                i, record.ttl, RECORD_MIN_TTL
+            ));
+        }
+    }
+
+    let size = gen_records(records).as_slice().len();
+    if size > RECORD_SIZE_LIMIT as usize {
+        errors.push(format!(
+            "The size of Records is {} bytes, it is more than the record_size_limit {}.",
+            size, RECORD_SIZE_LIMIT
+        ));
+    }
+
+    errors
+}
@@ -0,0 +1,66 @@
+use ckb_hash::blake2b_256;
+use std::convert::TryInto;
+
+use super::network::Network;
+use super::price::RATE_BASE;
+
+/// Accounts of this length or longer are not limited by the lucky number.
+pub const RELEASE_FREE_LENGTH: usize = 10;
+
+/// The rate of accounts shorter than RELEASE_FREE_LENGTH which are released, 6000 means 60%.
+pub fn release_rate_of(network: Network) -> u32 {
+    match network {
+        Network::Mainnet => 6000,
+        Network::Testnet | Network::Dev => 6000,
+    }
+}
+
+/// The lucky_number of ConfigCellRelease which releases the rate of accounts, like 2147483647 for 50%.
+pub fn lucky_number_of(rate: u32) -> u32 {
+    if rate as u64 > RATE_BASE {
+        panic!("The release rate {} should be no more than {}.", rate, RATE_BASE);
+    }
+
+    (u32::MAX as u64 * rate as u64 / RATE_BASE) as u32
+}
+
+/// The lucky number of an account is the first 4 bytes of the blake2b_256 hash of the account with .bit in big endian.
+pub fn lucky_number_of_account(account: &str) -> u32 {
+    let hash = blake2b_256(format!("{}.bit", account.trim_end_matches(".bit")).as_bytes());
+    u32::from_be_bytes(hash[..4].try_into().unwrap())
+}
+
+/// Check if an account can be registered with the lucky_number of ConfigCellRelease, the length is counted by
+/// characters.
+pub fn is_released(account: &str, length: usize, lucky_number: u32) -> bool {
+    length >= RELEASE_FREE_LENGTH || lucky_number_of_account(account) <= lucky_number
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lucky_number_of() {
+        assert_eq!(lucky_number_of(0), 0);
+        assert_eq!(lucky_number_of(5000), 2_147_483_647);
+        assert_eq!(lucky_number_of(6000), 2_576_980_377);
+        assert_eq!(lucky_number_of(10000), u32::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_lucky_number_of_rate_more_than_base() {
+        lucky_number_of(10001);
+    }
+
+    #[test]
+    fn test_is_released() {
+        // Every account is released with the max lucky number, and none of the short ones with 0 unless its own
+        // lucky number is 0.
+        assert!(is_released("das", 3, u32::MAX));
+        assert_eq!(is_released("das", 3, 0), lucky_number_of_account("das") == 0);
+        assert!(is_released("0123456789", 10, 0));
+        assert_eq!(lucky_number_of_account("das"), lucky_number_of_account("das.bit"));
+    }
+}
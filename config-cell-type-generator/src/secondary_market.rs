@@ -0,0 +1,145 @@
+use serde::Deserialize;
+use std::{fs, path::Path};
+
+use super::capacity::format_ckb;
+use super::network::Network;
+
+/// The limits of sales and offers in ConfigCellSecondaryMarket, prices are in shannons and durations are in seconds.
+#[derive(Debug, Clone)]
+pub struct SecondaryMarketConfig {
+    pub common_fee: u64,
+    pub sale_min_price: u64,
+    pub sale_expiration_limit: u64,
+    pub sale_description_bytes_limit: usize,
+    pub offer_min_price: u64,
+    pub offer_message_bytes_limit: usize,
+}
+
+const SECONDARY_MARKET_CONFIG: SecondaryMarketConfig = SecondaryMarketConfig {
+    common_fee: 10_000,
+    sale_min_price: 20_000_000_000,
+    sale_expiration_limit: 86400 * 30,
+    sale_description_bytes_limit: 5000,
+    offer_min_price: 100_000_000_000,
+    offer_message_bytes_limit: 5000,
+};
+
+pub fn secondary_market_config_of(network: Network) -> SecondaryMarketConfig {
+    // The limits are the same on all networks for now.
+    match network {
+        Network::Mainnet | Network::Testnet | Network::Dev => SECONDARY_MARKET_CONFIG,
+    }
+}
+
+/// A sale or an offer proposed by marketplaces, like {"type": "sale", "account": "das.bit", "price": 20000000000, ...} .
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum MarketOrder {
+    Sale(Sale),
+    Offer(Offer),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Sale {
+    pub account: String,
+    pub price: u64,
+    #[serde(default)]
+    pub description: String,
+    pub started_at: u64,
+    pub expired_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Offer {
+    pub account: String,
+    pub price: u64,
+    #[serde(default)]
+    pub message: String,
+}
+
+pub fn load_market_order(path: &Path) -> MarketOrder {
+    let data = fs::read_to_string(path).expect(format!("Expect file {} exist.", path.display()).as_str());
+    serde_json::from_str(&data).expect(format!("Expect file {} to be a valid sale or offer.", path.display()).as_str())
+}
+
+fn check_account(account: &str) -> Option<String> {
+    match account.strip_suffix(".bit") {
+        Some(name) if !name.is_empty() => None,
+        _ => Some(format!(
+            "The account {} should be a .bit account, like das.bit .",
+            account
+        )),
+    }
+}
+
+fn check_bytes_limit(field: &str, value: &str, limit_name: &str, limit: usize) -> Option<String> {
+    if value.len() > limit {
+        Some(format!(
+            "The {} is {} bytes in UTF-8, it should be no more than {} {} bytes.",
+            field,
+            value.len(),
+            limit_name,
+            limit
+        ))
+    } else {
+        None
+    }
+}
+
+/// Check a sale or an offer against ConfigCellSecondaryMarket, every violation is explained with the limit it breaks.
+pub fn check_market_order(config: &SecondaryMarketConfig, order: &MarketOrder) -> Vec<String> {
+    let mut errors = Vec::new();
+    match order {
+        MarketOrder::Sale(sale) => {
+            errors.extend(check_account(&sale.account));
+            if sale.price < config.sale_min_price {
+                errors.push(format!(
+                    "The price {} CKB is less than sale_min_price {} CKB.",
+                    format_ckb(sale.price),
+                    format_ckb(config.sale_min_price)
+                ));
+            }
+            errors.extend(check_bytes_limit(
+                "description",
+                &sale.description,
+                "sale_description_bytes_limit",
+                config.sale_description_bytes_limit,
+            ));
+            if let Some(expired_at) = sale.expired_at {
+                if expired_at <= sale.started_at {
+                    errors.push(format!(
+                        "The expired_at {} should be later than the started_at {}.",
+                        expired_at, sale.started_at
+                    ));
+                } else if expired_at - sale.started_at > config.sale_expiration_limit {
+                    errors.push(format!(
+                        "The sale lasts {} seconds, more than sale_expiration_limit {} seconds, it should expire by {}.",
+                        expired_at - sale.started_at,
+                        config.sale_expiration_limit,
+                        sale.started_at + config.sale_expiration_limit
+                    ));
+                }
+            }
+        }
+        MarketOrder::Offer(offer) => {
+            errors.extend(check_account(&offer.account));
+            if offer.price < config.offer_min_price {
+                errors.push(format!(
+                    "The price {} CKB is less than offer_min_price {} CKB.",
+                    format_ckb(offer.price),
+                    format_ckb(config.offer_min_price)
+                ));
+            }
+            errors.extend(check_bytes_limit(
+                "message",
+                &offer.message,
+                "offer_message_bytes_limit",
+                config.offer_message_bytes_limit,
+            ));
+        }
+    }
+
+    errors
+}
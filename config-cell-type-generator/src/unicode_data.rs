@@ -0,0 +1,155 @@
+use std::{collections::HashMap, ops::RangeInclusive, path::Path};
+
+use super::util::read_lines_in;
+
+/// The directory in ./data which contains the Unicode Character Database files the char sets are generated from.
+pub const UNICODE_DATA_DIR: &str = "unicode";
+/// The directory in ./data which contains the files derived from the Unicode data of Perl, they are used when the
+/// official files are not in UNICODE_DATA_DIR.
+pub const DERIVED_UNICODE_DATA_DIR: &str = "unicode/derived";
+
+type NamedRanges = HashMap<String, Vec<RangeInclusive<u32>>>;
+
+/// The blocks, scripts, emoji properties and ages of code points loaded from the files in ./data/unicode .
+pub struct UnicodeData {
+    blocks: NamedRanges,
+    scripts: NamedRanges,
+    properties: NamedRanges,
+    ages: Vec<(RangeInclusive<u32>, (u32, u32))>,
+    /// The files which are loaded from DERIVED_UNICODE_DATA_DIR instead of the official ones.
+    pub derived_files: Vec<String>,
+}
+
+/// The path of a file in ./data, the official file in UNICODE_DATA_DIR is preferred over the derived one.
+fn unicode_data_path(data_dir: &Path, file: &str) -> (String, bool) {
+    let path = format!("{}/{}", UNICODE_DATA_DIR, file);
+    if data_dir.join(&path).is_file() {
+        (path, false)
+    } else {
+        (format!("{}/{}", DERIVED_UNICODE_DATA_DIR, file), true)
+    }
+}
+
+/// Parse a file in the format of the Unicode Character Database, every line is like `0000..007F; Basic Latin` or
+/// `00AA ; Latin`, the content after # is comment.
+fn load_named_ranges(data_dir: &Path, path: &str) -> Vec<(RangeInclusive<u32>, String)> {
+    let lines = read_lines_in(data_dir, path).expect(format!("Expect file ./data/{} exist.", path).as_str());
+
+    let mut ret = Vec::new();
+    for line in lines {
+        if let Ok(line) = line {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut fields = line.split(';');
+            let range = fields.next().unwrap().trim();
+            let name = fields
+                .next()
+                .expect(format!("File ./data/{} line {} is invalid.", path, line).as_str())
+                .trim();
+            let range = parse_code_point_range(range)
+                .expect(format!("File ./data/{} line {} is invalid.", path, line).as_str());
+
+            ret.push((range, name.to_string()));
+        }
+    }
+
+    ret
+}
+
+fn group_by_name(ranges: Vec<(RangeInclusive<u32>, String)>) -> NamedRanges {
+    let mut ret: NamedRanges = HashMap::new();
+    for (range, name) in ranges {
+        ret.entry(name).or_default().push(range);
+    }
+
+    ret
+}
+
+/// Parse a code point or a range of code points in hex, like `0061` or `0061..007A`.
+pub fn parse_code_point_range(value: &str) -> Result<RangeInclusive<u32>, String> {
+    let parse = |code_point: &str| {
+        u32::from_str_radix(code_point.trim(), 16).map_err(|_| format!("{} is not a valid code point", code_point))
+    };
+
+    let range = match value.split_once("..") {
+        Some((start, end)) => parse(start)?..=parse(end)?,
+        None => parse(value)?..=parse(value)?,
+    };
+    if range.start() > range.end() || *range.end() > 0x10FFFF {
+        return Err(format!("{} is not a valid range of code points", value));
+    }
+
+    Ok(range)
+}
+
+/// Parse an age like `11.0` into the major and minor version.
+pub fn parse_age(value: &str) -> Result<(u32, u32), String> {
+    let (major, minor) = value.split_once('.').unwrap_or((value, "0"));
+    match (major.parse(), minor.parse()) {
+        (Ok(major), Ok(minor)) => Ok((major, minor)),
+        _ => Err(format!("{} is not a valid Unicode version", value)),
+    }
+}
+
+impl UnicodeData {
+    pub fn load(data_dir: &Path) -> Self {
+        let mut derived_files = Vec::new();
+        let mut load = |file: &str| {
+            let (path, is_derived) = unicode_data_path(data_dir, file);
+            if is_derived {
+                derived_files.push(path.clone());
+            }
+            load_named_ranges(data_dir, &path)
+        };
+
+        let ages = load("DerivedAge.txt")
+            .into_iter()
+            .map(|(range, age)| {
+                let age = parse_age(&age).expect(format!("The age {} in DerivedAge.txt is invalid.", age).as_str());
+                (range, age)
+            })
+            .collect();
+        let blocks = group_by_name(load("Blocks.txt"));
+        let scripts = group_by_name(load("Scripts.txt"));
+        let properties = group_by_name(load("emoji-data.txt"));
+
+        UnicodeData {
+            blocks,
+            scripts,
+            properties,
+            ages,
+            derived_files,
+        }
+    }
+
+    pub fn block(&self, name: &str) -> Option<&[RangeInclusive<u32>]> {
+        self.blocks.get(name).map(|ranges| ranges.as_slice())
+    }
+
+    pub fn script(&self, name: &str) -> Option<&[RangeInclusive<u32>]> {
+        self.scripts.get(name).map(|ranges| ranges.as_slice())
+    }
+
+    pub fn property(&self, name: &str) -> Option<&[RangeInclusive<u32>]> {
+        self.properties.get(name).map(|ranges| ranges.as_slice())
+    }
+
+    pub fn has_property(&self, name: &str, code_point: u32) -> bool {
+        self.property(name)
+            .map(|ranges| ranges.iter().any(|range| range.contains(&code_point)))
+            .unwrap_or(false)
+    }
+
+    /// Return the version of Unicode which the code point is assigned in, None means it is unassigned.
+    pub fn age(&self, code_point: u32) -> Option<(u32, u32)> {
+        // The ranges in DerivedAge.txt are sorted and do not overlap.
+        let index = self.ages.partition_point(|(range, _)| *range.end() < code_point);
+        self.ages
+            .get(index)
+            .filter(|(range, _)| range.contains(&code_point))
+            .map(|(_, age)| *age)
+    }
+}
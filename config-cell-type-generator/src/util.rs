@@ -6,15 +6,25 @@ use std::{
     io::{BufRead, BufReader, Lines, Result},
     env,
     io,
-    path::PathBuf
+    path::{Path, PathBuf},
 };
 
-pub fn read_lines(file_name: &str) -> Result<Lines<BufReader<File>>> {
+pub fn data_dir() -> PathBuf {
     let dir = env::current_exe().unwrap();
     let project_dir = dir.parent().unwrap().parent().unwrap().parent().unwrap();
-    let mut file_path = PathBuf::new();
-    file_path.push(project_dir);
-    file_path.push("data");
+    let mut data_dir = PathBuf::new();
+    data_dir.push(project_dir);
+    data_dir.push("data");
+
+    data_dir
+}
+
+pub fn read_lines(file_name: &str) -> Result<Lines<BufReader<File>>> {
+    read_lines_in(&data_dir(), file_name)
+}
+
+pub fn read_lines_in(data_dir: &Path, file_name: &str) -> Result<Lines<BufReader<File>>> {
+    let mut file_path = data_dir.to_path_buf();
     file_path.push(file_name);
 
     // Read record keys from file, then sort them.
@@ -42,9 +52,31 @@ pub fn prepend_molecule_like_length(raw: Vec<u8>) -> Vec<u8> {
     entity
 }
 
+/// Wrap the entity of a ConfigCell as its witness, the structure is "das" + config type (4 bytes LE) + entity.
+pub fn wrap_config_witness(config_type: u32, entity: &[u8]) -> Vec<u8> {
+    let mut witness = b"das".to_vec();
+    witness.extend(&config_type.to_le_bytes());
+    witness.extend(entity);
+
+    witness
+}
+
+pub const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
 pub fn gen_timestamp(datetime: &str) -> u64 {
-    let navie_datetime =
-        NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M:%S").expect("Invalid datetime format.");
+    parse_timestamp(datetime).expect("Invalid datetime format.")
+}
+
+/// Parse a datetime like 2022-01-01 00:00:00 in UTC into a unix timestamp, None if the format is invalid.
+pub fn parse_timestamp(datetime: &str) -> Option<u64> {
+    let navie_datetime = NaiveDateTime::parse_from_str(datetime, DATETIME_FORMAT).ok()?;
     let datetime = DateTime::<Utc>::from_utc(navie_datetime, Utc);
-    datetime.timestamp() as u64
+    Some(datetime.timestamp() as u64)
+}
+
+pub fn format_timestamp(timestamp: u64) -> String {
+    let navie_datetime = NaiveDateTime::from_timestamp(timestamp as i64, 0);
+    DateTime::<Utc>::from_utc(navie_datetime, Utc)
+        .format("%Y-%m-%d %H:%M:%S UTC")
+        .to_string()
 }
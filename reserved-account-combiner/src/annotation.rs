@@ -0,0 +1,152 @@
+use chrono::NaiveDate;
+use regex::Regex;
+use std::borrow::Cow;
+
+use super::reservation::*;
+use super::variant::VariantRule;
+
+/// Annotations of accounts in raw files, they are declared by HTML comments like
+/// `<!-- reserved_from=2021-07-22 reserved_until=2022-07-22 variants=suffix:nft,suffix:dao -->`.
+///
+/// When the comment is on a line without any account, it applies to all the following accounts in the same file until
+/// the next comment declaring the same fields, otherwise it only applies to the accounts on the same line. Every field
+/// is inherited separately, so a comment declaring only reserved_from keeps the reserved_until declared before.
+#[derive(Debug, Clone, Default)]
+pub struct Annotation {
+    pub period: Period,
+    pub variants: Option<Vec<VariantRule>>,
+}
+
+impl Annotation {
+    /// Override the fields of self with the fields declared in other.
+    pub fn merge(&self, other: &Annotation) -> Annotation {
+        Annotation {
+            period: Period {
+                from: other.period.from.or(self.period.from),
+                until: other.period.until.or(self.period.until),
+            },
+            variants: other.variants.clone().or_else(|| self.variants.clone()),
+        }
+    }
+}
+
+pub struct AnnotationParser {
+    comment_re: Regex,
+    period_re: Regex,
+    variants_re: Regex,
+}
+
+impl AnnotationParser {
+    pub fn new() -> Self {
+        AnnotationParser {
+            comment_re: Regex::new(r"<!--(.*?)-->").unwrap(),
+            period_re: Regex::new(r"(reserved_from|reserved_until)\s*=\s*([0-9]{4}-[0-9]{2}-[0-9]{2})").unwrap(),
+            variants_re: Regex::new(r"variants\s*=\s*([^\s]+)").unwrap(),
+        }
+    }
+
+    /// Parse the annotation declared in the line and return the line without the comment.
+    pub fn parse<'a>(&self, source: &str, line: &'a str) -> (Annotation, Cow<'a, str>) {
+        let mut ret = Annotation::default();
+        for comment in self.comment_re.captures_iter(line) {
+            for field in self.period_re.captures_iter(&comment[1]) {
+                let date = NaiveDate::parse_from_str(&field[2], DATE_FORMAT)
+                    .expect(format!("{} contains invalid date: {}", source, &field[2]).as_str());
+                match &field[1] {
+                    "reserved_from" => ret.period.from = Some(date),
+                    _ => ret.period.until = Some(date),
+                }
+            }
+
+            if let Some(field) = self.variants_re.captures(&comment[1]) {
+                let variants = field[1]
+                    .split(',')
+                    .map(|rule| {
+                        rule.parse::<VariantRule>()
+                            .expect(format!("{} contains invalid variant rule: {}", source, rule).as_str())
+                    })
+                    .collect();
+                ret.variants = Some(variants);
+            }
+        }
+
+        (ret, self.comment_re.replace_all(line, ""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_annotation() {
+        let parser = AnnotationParser::new();
+        let (annotation, line) = parser.parse(
+            "brands.md",
+            "apple.bit <!-- reserved_from=2021-07-22 reserved_until=2022-07-22 variants=suffix:nft,replace:o=0 -->",
+        );
+
+        assert_eq!(line, "apple.bit ");
+        assert_eq!(
+            annotation.period,
+            Period {
+                from: Some(NaiveDate::from_ymd(2021, 7, 22)),
+                until: Some(NaiveDate::from_ymd(2022, 7, 22)),
+            }
+        );
+        assert_eq!(
+            annotation.variants,
+            Some(vec![
+                VariantRule::Suffix(String::from("nft")),
+                VariantRule::Replace('o', '0')
+            ])
+        );
+
+        let (annotation, line) = parser.parse("brands.md", "apple.bit google.bit");
+        assert_eq!(line, "apple.bit google.bit");
+        assert_eq!(annotation.period, Period::default());
+        assert_eq!(annotation.variants, None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_parse_annotation_with_invalid_date() {
+        AnnotationParser::new().parse("brands.md", "<!-- reserved_until=2022-13-01 -->");
+    }
+
+    #[test]
+    fn test_merge_annotation_by_fields() {
+        let parser = AnnotationParser::new();
+        let (file_annotation, _) = parser.parse("brands.md", "<!-- reserved_until=2022-07-22 variants=prefix:the -->");
+        let (line_annotation, _) = parser.parse("brands.md", "apple.bit <!-- reserved_from=2021-07-22 -->");
+
+        // The line only declares reserved_from, so reserved_until and variants are inherited from the file.
+        let annotation = file_annotation.merge(&line_annotation);
+        assert_eq!(
+            annotation.period,
+            Period {
+                from: Some(NaiveDate::from_ymd(2021, 7, 22)),
+                until: Some(NaiveDate::from_ymd(2022, 7, 22)),
+            }
+        );
+        assert_eq!(
+            annotation.variants,
+            Some(vec![VariantRule::Prefix(String::from("the"))])
+        );
+
+        // A later comment of the file overrides only the fields it declares.
+        let (next_annotation, _) = parser.parse("brands.md", "<!-- reserved_until=2023-01-01 -->");
+        let file_annotation = file_annotation.merge(&next_annotation);
+        assert_eq!(
+            file_annotation.period,
+            Period {
+                from: None,
+                until: Some(NaiveDate::from_ymd(2023, 1, 1)),
+            }
+        );
+        assert_eq!(
+            file_annotation.variants,
+            Some(vec![VariantRule::Prefix(String::from("the"))])
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::{collections::HashSet, fs, path::Path};
+
+use super::reservation::*;
+use super::util::*;
+use super::variant::VariantRule;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplicationStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+/// An application for reserving accounts, every application is a TOML file in the applications directory.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Application {
+    pub id: String,
+    pub applicant: String,
+    pub brand: String,
+    pub names: Vec<String>,
+    #[serde(default)]
+    pub evidence: Vec<String>,
+    pub status: ApplicationStatus,
+    pub reserved_from: Option<String>,
+    pub reserved_until: Option<String>,
+    /// Variant rules like `suffix:nft`, they apply to all the names of the application.
+    #[serde(default)]
+    pub variants: Vec<String>,
+}
+
+impl Application {
+    pub fn source(&self) -> String {
+        format!("application {}", self.id)
+    }
+
+    pub fn period(&self) -> Period {
+        let parse = |date: &Option<String>| {
+            date.as_ref().map(|date| {
+                NaiveDate::parse_from_str(date, DATE_FORMAT)
+                    .expect(format!("Application {} contains invalid date: {}", self.id, date).as_str())
+            })
+        };
+
+        Period {
+            from: parse(&self.reserved_from),
+            until: parse(&self.reserved_until),
+        }
+    }
+
+    pub fn variants(&self) -> Vec<VariantRule> {
+        self.variants
+            .iter()
+            .map(|rule| {
+                rule.parse::<VariantRule>()
+                    .expect(format!("Application {} contains invalid variant rule: {}", self.id, rule).as_str())
+            })
+            .collect()
+    }
+
+    pub fn accounts(&self) -> Vec<String> {
+        self.names
+            .iter()
+            .map(|name| name.trim().trim_end_matches(".bit").to_ascii_lowercase())
+            .collect()
+    }
+}
+
+pub fn load_applications(dir: &str) -> Vec<Application> {
+    if !Path::new(dir).exists() {
+        return Vec::new();
+    }
+
+    let mut applications: Vec<Application> = Vec::new();
+    for item in fs::read_dir(dir).expect(format!("{} should be a directory.", dir).as_str()) {
+        if let Ok(file) = item {
+            let path = file.path();
+            if !path.is_file() || path.extension().map(|ext| ext != "toml").unwrap_or(true) {
+                continue;
+            }
+
+            let data = fs::read_to_string(&path)
+                .expect(format!("{} should be a readable file.", path.to_str().unwrap()).as_str());
+            let application: Application = toml::from_str(&data)
+                .expect(format!("{} should be a valid application.", path.to_str().unwrap()).as_str());
+            if applications.iter().any(|item| item.id == application.id) {
+                panic!("Application id {} is duplicated.", application.id);
+            }
+
+            applications.push(application);
+        }
+    }
+
+    applications.sort_by(|a, b| a.id.cmp(&b.id));
+    applications
+}
+
+/// Load the registered accounts exported from the chain, one account per line, the .bit suffix is optional.
+pub fn load_registered_accounts(file: &str) -> HashSet<String> {
+    fs::read_to_string(file)
+        .expect(format!("{} should be a readable file.", file).as_str())
+        .lines()
+        .map(|line| line.trim().trim_end_matches(".bit").to_ascii_lowercase())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Add the accounts of approved applications, the accounts which have been registered are rejected and returned.
+pub fn merge_applications(
+    reserved_accounts: &mut ReservedAccounts,
+    applications: &[Application],
+    registered_accounts: &HashSet<String>,
+) -> Vec<(String, String)> {
+    let mut rejected = Vec::new();
+    for application in applications {
+        if application.status != ApplicationStatus::Approved {
+            continue;
+        }
+
+        if application.evidence.is_empty() {
+            panic!("Application {} is approved without any evidence.", application.id);
+        }
+
+        let period = application.period();
+        let variants = application.variants();
+        for account in application.accounts() {
+            if registered_accounts.contains(&account) {
+                rejected.push((
+                    account,
+                    format!(
+                        "{} of {} for {}",
+                        application.source(),
+                        application.applicant,
+                        application.brand
+                    ),
+                ));
+                continue;
+            }
+
+            let reservations = reserved_accounts.entry(account).or_default();
+            reservations.push(Reservation {
+                source: application.source(),
+                period,
+                variants: variants.clone(),
+                variant_of: None,
+            });
+            reservations.sort_by(|a, b| a.source.cmp(&b.source));
+        }
+    }
+
+    rejected
+}
+
+pub fn print_rejected(rejected: &[(String, String)]) {
+    println!("Rejected {} accounts which have been registered:", rejected.len());
+    for (account, source) in rejected {
+        println!("{}.bit from {}", account, source);
+    }
+    println!();
+}
@@ -0,0 +1,4 @@
+// These must be kept the same as the constants in das-types, because the generator groups reserved accounts with them.
+pub const PRESERVED_ACCOUNT_CELL_COUNT: u8 = 20;
+pub const PRESERVED_ACCOUNT_DATA_TYPE_START: u32 = 10000;
+pub const PRESERVED_ACCOUNT_LIMIT_PER_CELL: usize = 10_000;
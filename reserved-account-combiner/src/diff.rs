@@ -0,0 +1,101 @@
+use super::util::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+pub struct Change {
+    pub account: String,
+    pub sources: Vec<String>,
+    pub group: u8,
+}
+
+pub struct Diff {
+    pub added: Vec<Change>,
+    pub removed: Vec<Change>,
+}
+
+impl Diff {
+    /// Compare the accounts collected in this run with the accounts generated by previous run.
+    ///
+    /// The sources of removed accounts can only be found when the raw files of previous run are provided.
+    pub fn new(current: &ReservedAccounts, previous: &[String], base: Option<&ReservedAccounts>) -> Self {
+        let previous = previous.iter().collect::<BTreeSet<_>>();
+
+        let added = current
+            .iter()
+            .filter(|(account, _)| !previous.contains(account))
+            .map(|(account, reservations)| Change {
+                account: account.clone(),
+                sources: sources_of(reservations),
+                group: account_to_group(account),
+            })
+            .collect();
+
+        let removed = previous
+            .into_iter()
+            .filter(|account| !current.contains_key(*account))
+            .map(|account| Change {
+                account: account.clone(),
+                sources: base
+                    .and_then(|base| base.get(account))
+                    .map(|reservations| sources_of(reservations))
+                    .unwrap_or_default(),
+                group: account_to_group(account),
+            })
+            .collect();
+
+        Diff { added, removed }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    pub fn print(&self) {
+        println!("Added {} reserved accounts:", self.added.len());
+        for change in self.added.iter() {
+            print_change("+", change);
+        }
+        println!();
+
+        println!("Removed {} reserved accounts:", self.removed.len());
+        for change in self.removed.iter() {
+            print_change("-", change);
+        }
+        println!();
+
+        let mut groups: BTreeMap<u8, (usize, usize)> = BTreeMap::new();
+        for change in self.added.iter() {
+            groups.entry(change.group).or_default().0 += 1;
+        }
+        for change in self.removed.iter() {
+            groups.entry(change.group).or_default().1 += 1;
+        }
+
+        println!("All ConfigCells which need to be updated:");
+        for (group, (added, removed)) in groups {
+            println!(
+                "0x{} (group {}, +{} -{})",
+                hex::encode(group_to_data_type(group).to_le_bytes()),
+                group,
+                added,
+                removed
+            );
+        }
+    }
+}
+
+fn print_change(sign: &str, change: &Change) {
+    let sources = if change.sources.is_empty() {
+        String::from("unknown source")
+    } else {
+        change.sources.join(", ")
+    };
+
+    println!(
+        "{} {}.bit from {} => group {} (0x{})",
+        sign,
+        change.account,
+        sources,
+        change.group,
+        hex::encode(group_to_data_type(change.group).to_le_bytes())
+    );
+}
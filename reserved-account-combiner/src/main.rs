@@ -1,7 +1,20 @@
+use chrono::NaiveDate;
 use clap::Clap;
-use regex::Regex;
 use std::fs;
 
+mod annotation;
+mod application;
+mod constants;
+mod diff;
+mod reservation;
+mod util;
+mod variant;
+use application::*;
+use diff::Diff;
+use reservation::*;
+use util::*;
+use variant::*;
+
 #[derive(Clap, Debug)]
 #[clap(version = "1.0", author = "Link Xie. <xieaolin@gmail.com>")]
 struct Options {
@@ -17,39 +30,152 @@ struct Options {
         about = "Output file for generating a single file contains all reserved accounts."
     )]
     output: Option<String>,
+    #[clap(
+        long = "dry-run",
+        about = "Only report the added and removed accounts compared with the output file, nothing will be written."
+    )]
+    dry_run: bool,
+    #[clap(
+        long = "base-input",
+        about = "Input directory of the previous run, it is used to find out where the removed accounts come from."
+    )]
+    base_input: Option<String>,
+    #[clap(
+        long = "applications",
+        about = "Directory of application files, only the accounts of approved applications will be reserved."
+    )]
+    applications: Option<String>,
+    #[clap(
+        long = "registered",
+        about = "File contains all registered accounts, the accounts of applications in it will be rejected."
+    )]
+    registered: Option<String>,
+    #[clap(
+        long = "as-of",
+        about = "Drop the accounts whose reservations have expired or not started as of the date, like 2022-07-22."
+    )]
+    as_of: Option<String>,
+    #[clap(
+        long = "keep-expired",
+        about = "Only report the expired accounts instead of dropping them, this requires --as-of."
+    )]
+    keep_expired: bool,
+    #[clap(
+        long = "schedule",
+        about = "Only print the release schedule of reserved accounts per ConfigCell group, nothing will be written."
+    )]
+    schedule: bool,
+    #[clap(
+        long = "accept-variants",
+        about = "Reserve the variants expanded by variant rules, otherwise they are only reported."
+    )]
+    accept_variants: bool,
 }
 
 fn main() {
     let options: Options = Options::parse();
     // println!("{:?}", options);
 
-    let input = if options.input.is_some() {
-        options.input.unwrap()
-    } else {
-        String::from("./raw-reserved-accounts")
-    };
-    let output = if options.output.is_some() {
-        options.output.unwrap()
-    } else {
-        String::from("./data/reserved_accounts.txt")
-    };
-
-    let account_re = Regex::new(r"([^\s]+\.bit)").unwrap();
-    let mut reserved_accounts = Vec::new();
-    for item in fs::read_dir(&input).expect(format!("{} should be a directory.", input).as_str()) {
-        if let Ok(file) = item {
-            let data = fs::read_to_string(file.path())
-                .expect(format!("{} should be a readable file.", file.path().to_str().unwrap()).as_str());
-            let mut accounts = account_re
-                .find_iter(&data)
-                .map(|item| item.as_str().trim_end_matches(".bit").to_ascii_lowercase())
-                .collect::<Vec<_>>();
-            reserved_accounts.append(&mut accounts);
+    let input = options
+        .input
+        .clone()
+        .unwrap_or_else(|| String::from("./raw-reserved-accounts"));
+    let output = options
+        .output
+        .clone()
+        .unwrap_or_else(|| String::from("./data/reserved_accounts.txt"));
+
+    let as_of = options.as_of.as_ref().map(|as_of| {
+        NaiveDate::parse_from_str(as_of, DATE_FORMAT).expect(format!("{} should be a valid date.", as_of).as_str())
+    });
+    if options.keep_expired && as_of.is_none() {
+        panic!("--keep-expired only works with --as-of, please specify the date to find the expired accounts.");
+    }
+
+    let applications = options
+        .applications
+        .clone()
+        .unwrap_or_else(|| format!("{}/applications", input));
+
+    let mut reserved_accounts = collect_reserved_accounts(&input);
+    let registered_accounts = options
+        .registered
+        .as_ref()
+        .map(|registered| load_registered_accounts(registered))
+        .unwrap_or_default();
+    let mut rejected = merge_applications(
+        &mut reserved_accounts,
+        &load_applications(&applications),
+        &registered_accounts,
+    );
+    let (variants, rejected_variants) = expand_variants(&reserved_accounts, &registered_accounts);
+    rejected.extend(rejected_variants);
+    if !rejected.is_empty() {
+        print_rejected(&rejected);
+    }
+
+    if !variants.is_empty() {
+        print_variants(&variants, &reserved_accounts);
+        if options.accept_variants {
+            reserved_accounts.extend(variants);
+        } else {
+            println!("The variants are not reserved, please check them and run with --accept-variants.");
+            println!();
+        }
+    }
+
+    if options.schedule {
+        print_schedule(&reserved_accounts, as_of);
+        return;
+    }
+
+    if let Some(as_of) = as_of {
+        let expired = find_expired(&reserved_accounts, as_of);
+        if !expired.is_empty() {
+            print_expired(&expired, as_of);
+        }
+
+        if !options.keep_expired {
+            for account in expired.keys() {
+                reserved_accounts.remove(account);
+            }
+        }
+
+        let not_started = find_not_started(&reserved_accounts, as_of);
+        if !not_started.is_empty() {
+            print_not_started(&not_started, as_of);
+            for account in not_started.keys() {
+                reserved_accounts.remove(account);
+            }
+        }
+    }
+
+    if options.dry_run {
+        let previous = load_combined_accounts(&output);
+        let base = options.base_input.as_ref().map(|base_input| {
+            let mut base = collect_reserved_accounts(base_input);
+            merge_applications(
+                &mut base,
+                &load_applications(&format!("{}/applications", base_input)),
+                &registered_accounts,
+            );
+            if options.accept_variants {
+                let (variants, _) = expand_variants(&base, &registered_accounts);
+                base.extend(variants);
+            }
+            base
+        });
+        let diff = Diff::new(&reserved_accounts, &previous, base.as_ref());
+
+        if diff.is_empty() {
+            println!("Nothing changed compared with {}.", output);
+        } else {
+            diff.print();
         }
+        return;
     }
 
-    reserved_accounts.sort();
-    reserved_accounts.dedup();
+    let reserved_accounts = reserved_accounts.into_keys().collect::<Vec<_>>();
 
     fs::write(&output, reserved_accounts.join("\n")).expect(format!("{} should be a writable file.", output).as_str());
 
@@ -0,0 +1,240 @@
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+
+use super::util::*;
+use super::variant::VariantRule;
+
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// The period of a reservation, both sides are optional.
+///
+/// It is declared in raw files by a HTML comment like `<!-- reserved_from=2021-07-22 reserved_until=2022-07-22 -->`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Period {
+    pub from: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Reservation {
+    pub source: String,
+    pub period: Period,
+    pub variants: Vec<VariantRule>,
+    /// The original account and the rule which expanded this account, it is None for accounts not expanded.
+    pub variant_of: Option<(String, VariantRule)>,
+}
+
+impl Reservation {
+    pub fn describe_source(&self) -> String {
+        match &self.variant_of {
+            Some((account, rule)) => format!("{} ({} of {}.bit)", self.source, rule, account),
+            None => self.source.clone(),
+        }
+    }
+}
+
+impl Period {
+    pub fn is_expired(&self, as_of: NaiveDate) -> bool {
+        match self.until {
+            Some(until) => until < as_of,
+            None => false,
+        }
+    }
+
+    pub fn is_started(&self, as_of: NaiveDate) -> bool {
+        match self.from {
+            Some(from) => from <= as_of,
+            None => true,
+        }
+    }
+
+    pub fn is_active(&self, as_of: NaiveDate) -> bool {
+        self.is_started(as_of) && !self.is_expired(as_of)
+    }
+}
+
+/// An account is released only when all of its reservations are ended, so the latest end date is the release date.
+pub fn release_date(reservations: &[Reservation]) -> Option<NaiveDate> {
+    let mut ret = None;
+    for reservation in reservations {
+        match reservation.period.until {
+            Some(until) => {
+                if ret.is_none() || ret < Some(until) {
+                    ret = Some(until)
+                }
+            }
+            None => return None,
+        }
+    }
+
+    ret
+}
+
+/// Find the accounts whose reservations have all expired as of the given date.
+pub fn find_expired(reserved_accounts: &ReservedAccounts, as_of: NaiveDate) -> ReservedAccounts {
+    reserved_accounts
+        .iter()
+        .filter(|(_, reservations)| reservations.iter().all(|item| item.period.is_expired(as_of)))
+        .map(|(account, reservations)| (account.clone(), reservations.clone()))
+        .collect()
+}
+
+/// Find the accounts which are not reserved yet as of the given date, that is none of their reservations is active and
+/// some of them have not started.
+pub fn find_not_started(reserved_accounts: &ReservedAccounts, as_of: NaiveDate) -> ReservedAccounts {
+    reserved_accounts
+        .iter()
+        .filter(|(_, reservations)| {
+            !reservations.iter().any(|item| item.period.is_active(as_of))
+                && reservations.iter().any(|item| !item.period.is_started(as_of))
+        })
+        .map(|(account, reservations)| (account.clone(), reservations.clone()))
+        .collect()
+}
+
+/// The earliest date when one of the reservations which have not started as of the given date starts.
+pub fn start_date(reservations: &[Reservation], as_of: NaiveDate) -> Option<NaiveDate> {
+    reservations
+        .iter()
+        .filter_map(|item| item.period.from)
+        .filter(|from| *from > as_of)
+        .min()
+}
+
+pub fn print_not_started(not_started: &ReservedAccounts, as_of: NaiveDate) {
+    println!(
+        "Excluded {} reserved accounts whose reservations have not started as of {}:",
+        not_started.len(),
+        as_of
+    );
+    for (account, reservations) in not_started.iter() {
+        println!(
+            "{}.bit from {} starts at {}",
+            account,
+            sources_of(reservations).join(", "),
+            start_date(reservations, as_of).unwrap()
+        );
+    }
+    println!();
+}
+
+pub fn print_expired(expired: &ReservedAccounts, as_of: NaiveDate) {
+    println!("Expired {} reserved accounts as of {}:", expired.len(), as_of);
+    for (account, reservations) in expired.iter() {
+        let group = account_to_group(account);
+        println!(
+            "{}.bit from {} expired at {} => group {} (0x{})",
+            account,
+            sources_of(reservations).join(", "),
+            release_date(reservations).unwrap(),
+            group,
+            hex::encode(group_to_data_type(group).to_le_bytes())
+        );
+    }
+    println!();
+}
+
+/// Print the upcoming releases of reserved accounts grouped by date and ConfigCell group.
+pub fn print_schedule(reserved_accounts: &ReservedAccounts, as_of: Option<NaiveDate>) {
+    let mut schedule: BTreeMap<(NaiveDate, u8), Vec<&String>> = BTreeMap::new();
+    for (account, reservations) in reserved_accounts.iter() {
+        if let Some(date) = release_date(reservations) {
+            if as_of.map(|as_of| date >= as_of).unwrap_or(true) {
+                schedule
+                    .entry((date, account_to_group(account)))
+                    .or_default()
+                    .push(account);
+            }
+        }
+    }
+
+    println!("Release schedule of reserved accounts:");
+    for ((date, group), accounts) in schedule {
+        println!(
+            "{} group {} (0x{}): {} accounts",
+            date,
+            group,
+            hex::encode(group_to_data_type(group).to_le_bytes()),
+            accounts.len()
+        );
+        for account in accounts {
+            println!("  {}.bit", account);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_expired_as_of() {
+        let mut reserved_accounts = ReservedAccounts::new();
+        reserved_accounts.insert(
+            String::from("apple"),
+            vec![Reservation {
+                source: String::from("brands.md"),
+                period: Period {
+                    from: None,
+                    until: Some(NaiveDate::from_ymd(2022, 7, 22)),
+                },
+                variants: Vec::new(),
+                variant_of: None,
+            }],
+        );
+        // An account is only expired when all of its reservations are expired.
+        reserved_accounts.insert(
+            String::from("google"),
+            vec![
+                Reservation {
+                    source: String::from("brands.md"),
+                    period: Period {
+                        from: None,
+                        until: Some(NaiveDate::from_ymd(2022, 7, 22)),
+                    },
+                    variants: Vec::new(),
+                    variant_of: None,
+                },
+                Reservation {
+                    source: String::from("companies.md"),
+                    period: Period::default(),
+                    variants: Vec::new(),
+                    variant_of: None,
+                },
+            ],
+        );
+
+        // The reservation is still active on the day of reserved_until.
+        assert!(find_expired(&reserved_accounts, NaiveDate::from_ymd(2022, 7, 22)).is_empty());
+        let expired = find_expired(&reserved_accounts, NaiveDate::from_ymd(2022, 7, 23));
+        assert_eq!(expired.keys().collect::<Vec<_>>(), vec!["apple"]);
+        assert_eq!(release_date(&expired["apple"]), Some(NaiveDate::from_ymd(2022, 7, 22)));
+        assert_eq!(release_date(&reserved_accounts["google"]), None);
+    }
+
+    #[test]
+    fn test_find_not_started_as_of() {
+        let mut reserved_accounts = ReservedAccounts::new();
+        reserved_accounts.insert(
+            String::from("apple"),
+            vec![Reservation {
+                source: String::from("brands.md"),
+                period: Period {
+                    from: Some(NaiveDate::from_ymd(2022, 1, 1)),
+                    until: None,
+                },
+                variants: Vec::new(),
+                variant_of: None,
+            }],
+        );
+
+        let as_of = NaiveDate::from_ymd(2021, 12, 31);
+        let not_started = find_not_started(&reserved_accounts, as_of);
+        assert_eq!(not_started.keys().collect::<Vec<_>>(), vec!["apple"]);
+        assert_eq!(
+            start_date(&not_started["apple"], as_of),
+            Some(NaiveDate::from_ymd(2022, 1, 1))
+        );
+        assert!(find_not_started(&reserved_accounts, NaiveDate::from_ymd(2022, 1, 1)).is_empty());
+    }
+}
@@ -0,0 +1,93 @@
+use super::annotation::*;
+use super::constants::*;
+use super::reservation::*;
+use ckb_hash::blake2b_256;
+use regex::Regex;
+use std::{collections::BTreeMap, fs, path::Path};
+
+/// Reserved accounts mapped to their reservations in the raw files.
+pub type ReservedAccounts = BTreeMap<String, Vec<Reservation>>;
+
+pub fn collect_reserved_accounts(input: &str) -> ReservedAccounts {
+    let account_re = Regex::new(r"([^\s]+\.bit)").unwrap();
+    let annotation_parser = AnnotationParser::new();
+    let mut reserved_accounts = ReservedAccounts::new();
+    for item in fs::read_dir(input).expect(format!("{} should be a directory.", input).as_str()) {
+        if let Ok(file) = item {
+            if !file.path().is_file() {
+                continue;
+            }
+
+            let source = file.file_name().to_string_lossy().to_string();
+            let data = fs::read_to_string(file.path())
+                .expect(format!("{} should be a readable file.", file.path().to_str().unwrap()).as_str());
+            let mut file_annotation = Annotation::default();
+            for line in data.lines() {
+                let (annotation, line) = annotation_parser.parse(&source, line);
+                let mut accounts = account_re.find_iter(&line).peekable();
+                if accounts.peek().is_none() {
+                    file_annotation = file_annotation.merge(&annotation);
+                    continue;
+                }
+
+                let annotation = file_annotation.merge(&annotation);
+                if let (Some(from), Some(until)) = (annotation.period.from, annotation.period.until) {
+                    if from > until {
+                        panic!(
+                            "{} contains a period which ends at {} before it starts at {}: {}",
+                            source, until, from, line
+                        );
+                    }
+                }
+                for item in accounts {
+                    let account = item.as_str().trim_end_matches(".bit").to_ascii_lowercase();
+                    reserved_accounts.entry(account).or_default().push(Reservation {
+                        source: source.clone(),
+                        period: annotation.period,
+                        variants: annotation.variants.clone().unwrap_or_default(),
+                        variant_of: None,
+                    });
+                }
+            }
+        }
+    }
+
+    for reservations in reserved_accounts.values_mut() {
+        reservations.sort_by(|a, b| a.source.cmp(&b.source));
+    }
+
+    reserved_accounts
+}
+
+pub fn sources_of(reservations: &[Reservation]) -> Vec<String> {
+    let mut sources = reservations
+        .iter()
+        .map(|item| item.describe_source())
+        .collect::<Vec<_>>();
+    sources.dedup();
+    sources
+}
+
+/// Load the accounts from a file generated by previous run, a missing file is treated as empty.
+pub fn load_combined_accounts(output: &str) -> Vec<String> {
+    if !Path::new(output).exists() {
+        return Vec::new();
+    }
+
+    fs::read_to_string(output)
+        .expect(format!("{} should be a readable file.", output).as_str())
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Calculate the index of the reserved account ConfigCell which the account belongs to, it is the same as what the
+/// config-cell-type-generator does.
+pub fn account_to_group(account: &str) -> u8 {
+    blake2b_256(account.as_bytes())[0] % PRESERVED_ACCOUNT_CELL_COUNT
+}
+
+pub fn group_to_data_type(group: u8) -> u32 {
+    PRESERVED_ACCOUNT_DATA_TYPE_START + group as u32
+}
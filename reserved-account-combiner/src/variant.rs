@@ -0,0 +1,234 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+
+use super::constants::*;
+use super::reservation::*;
+use super::util::*;
+
+/// The rules for expanding an account to its variants, they are written like `suffix:nft`, `prefix:the` or
+/// `replace:o=0`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariantRule {
+    Suffix(String),
+    Prefix(String),
+    /// Replace all the occurrences of a character with another one, like o with 0.
+    Replace(char, char),
+}
+
+impl FromStr for VariantRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, value) = s
+            .trim()
+            .split_once(':')
+            .ok_or_else(|| format!("Variant rule {} should be like kind:value.", s))?;
+        let value = value.to_ascii_lowercase();
+        if value.is_empty() {
+            return Err(format!("Variant rule {} should have a value.", s));
+        }
+
+        match kind {
+            "suffix" => Ok(VariantRule::Suffix(value)),
+            "prefix" => Ok(VariantRule::Prefix(value)),
+            "replace" => {
+                let chars = value.chars().collect::<Vec<_>>();
+                match chars.as_slice() {
+                    [from, '=', to] => Ok(VariantRule::Replace(*from, *to)),
+                    _ => Err(format!("Variant rule {} should be like replace:o=0.", s)),
+                }
+            }
+            _ => Err(format!("Variant rule {} has unknown kind {}.", s, kind)),
+        }
+    }
+}
+
+impl fmt::Display for VariantRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VariantRule::Suffix(value) => write!(f, "suffix:{}", value),
+            VariantRule::Prefix(value) => write!(f, "prefix:{}", value),
+            VariantRule::Replace(from, to) => write!(f, "replace:{}={}", from, to),
+        }
+    }
+}
+
+impl VariantRule {
+    /// Return None if the rule changes nothing.
+    pub fn apply(&self, account: &str) -> Option<String> {
+        let variant = match self {
+            VariantRule::Suffix(value) => format!("{}{}", account, value),
+            VariantRule::Prefix(value) => format!("{}{}", value, account),
+            VariantRule::Replace(from, to) => account.replace(*from, &to.to_string()),
+        };
+
+        if variant == account {
+            None
+        } else {
+            Some(variant)
+        }
+    }
+}
+
+/// Expand the accounts with variant rules, the variants which are already reserved are skipped, and the variants which
+/// have been registered are rejected with their sources.
+pub fn expand_variants(
+    reserved_accounts: &ReservedAccounts,
+    registered_accounts: &HashSet<String>,
+) -> (ReservedAccounts, Vec<(String, String)>) {
+    let mut variants = ReservedAccounts::new();
+    let mut rejected = Vec::new();
+    for (account, reservations) in reserved_accounts.iter() {
+        for reservation in reservations {
+            for rule in reservation.variants.iter() {
+                let variant = match rule.apply(account) {
+                    Some(variant) => variant,
+                    None => continue,
+                };
+                if reserved_accounts.contains_key(&variant) {
+                    continue;
+                }
+
+                let reservation = Reservation {
+                    source: reservation.source.clone(),
+                    period: reservation.period,
+                    variants: Vec::new(),
+                    variant_of: Some((account.clone(), rule.clone())),
+                };
+                if registered_accounts.contains(&variant) {
+                    rejected.push((variant, reservation.describe_source()));
+                    continue;
+                }
+
+                variants.entry(variant).or_default().push(reservation);
+            }
+        }
+    }
+
+    (variants, rejected)
+}
+
+/// Print the variants and how they change the sizes of reserved account ConfigCells.
+pub fn print_variants(variants: &ReservedAccounts, reserved_accounts: &ReservedAccounts) {
+    println!("Expanded {} variants of reserved accounts:", variants.len());
+    for (account, reservations) in variants.iter() {
+        let group = account_to_group(account);
+        let sources = reservations
+            .iter()
+            .map(|item| item.describe_source())
+            .collect::<Vec<_>>();
+        println!(
+            "{}.bit from {} => group {} (0x{})",
+            account,
+            sources.join(", "),
+            group,
+            hex::encode(group_to_data_type(group).to_le_bytes())
+        );
+    }
+    println!();
+
+    let mut groups: BTreeMap<u8, (usize, usize)> = BTreeMap::new();
+    for account in reserved_accounts.keys() {
+        groups.entry(account_to_group(account)).or_default().0 += 1;
+    }
+    for account in variants.keys() {
+        groups.entry(account_to_group(account)).or_default().1 += 1;
+    }
+
+    println!("Sizes of reserved account ConfigCells after accepting the variants:");
+    for (group, (current, added)) in groups {
+        let warning = if current + added > PRESERVED_ACCOUNT_LIMIT_PER_CELL {
+            " ❌ more than the limitation"
+        } else {
+            ""
+        };
+        println!(
+            "0x{} (group {}): {} + {} = {}{}",
+            hex::encode(group_to_data_type(group).to_le_bytes()),
+            group,
+            current,
+            added,
+            current + added,
+            warning
+        );
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_variant_rule() {
+        assert_eq!("suffix:NFT".parse(), Ok(VariantRule::Suffix(String::from("nft"))));
+        assert_eq!("prefix:the".parse(), Ok(VariantRule::Prefix(String::from("the"))));
+        assert_eq!("replace:o=0".parse(), Ok(VariantRule::Replace('o', '0')));
+        assert!("replace:o0".parse::<VariantRule>().is_err());
+        assert!("infix:x".parse::<VariantRule>().is_err());
+        assert!("suffix:".parse::<VariantRule>().is_err());
+    }
+
+    #[test]
+    fn test_apply_variant_rule() {
+        assert_eq!(
+            VariantRule::Suffix(String::from("nft")).apply("apple"),
+            Some(String::from("applenft"))
+        );
+        assert_eq!(
+            VariantRule::Prefix(String::from("the")).apply("apple"),
+            Some(String::from("theapple"))
+        );
+        assert_eq!(
+            VariantRule::Replace('o', '0').apply("google"),
+            Some(String::from("g00gle"))
+        );
+        assert_eq!(VariantRule::Replace('o', '0').apply("apple"), None);
+    }
+
+    #[test]
+    fn test_expand_variants() {
+        let mut reserved_accounts = ReservedAccounts::new();
+        reserved_accounts.insert(
+            String::from("google"),
+            vec![Reservation {
+                source: String::from("brands.md"),
+                period: Period::default(),
+                variants: vec![
+                    VariantRule::Suffix(String::from("nft")),
+                    VariantRule::Prefix(String::from("the")),
+                    VariantRule::Replace('o', '0'),
+                ],
+                variant_of: None,
+            }],
+        );
+        // The variant which is already reserved is skipped.
+        reserved_accounts.insert(
+            String::from("googlenft"),
+            vec![Reservation {
+                source: String::from("brands.md"),
+                period: Period::default(),
+                variants: Vec::new(),
+                variant_of: None,
+            }],
+        );
+        let registered_accounts = vec![String::from("thegoogle")].into_iter().collect::<HashSet<_>>();
+
+        let (variants, rejected) = expand_variants(&reserved_accounts, &registered_accounts);
+        assert_eq!(variants.keys().collect::<Vec<_>>(), vec!["g00gle"]);
+        assert_eq!(
+            variants["g00gle"][0].variant_of,
+            Some((String::from("google"), VariantRule::Replace('o', '0')))
+        );
+        assert_eq!(
+            rejected,
+            vec![(
+                String::from("thegoogle"),
+                String::from("brands.md (prefix:the of google.bit)")
+            )]
+        );
+    }
+}
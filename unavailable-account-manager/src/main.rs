@@ -0,0 +1,110 @@
+use clap::Clap;
+use std::{collections::HashSet, fs};
+
+mod util;
+use util::*;
+
+#[derive(Clap, Debug)]
+#[clap(version = "1.0", author = "Link Xie. <xieaolin@gmail.com>")]
+struct Options {
+    #[clap(
+        short = 'i',
+        long = "input",
+        about = "Private file contains the plaintext of unavailable accounts and their reason categories."
+    )]
+    input: Option<String>,
+    #[clap(
+        short = 'o',
+        long = "output",
+        about = "Public file contains the hashes of unavailable accounts."
+    )]
+    output: Option<String>,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Clap, Debug)]
+enum Command {
+    #[clap(about = "Append the hashes of new accounts in the private file to the public file.")]
+    Update,
+    #[clap(about = "Verify that every published hash has a matching account in the private file.")]
+    Verify,
+}
+
+fn main() {
+    let options: Options = Options::parse();
+    // println!("{:?}", options);
+
+    let input = options
+        .input
+        .clone()
+        .unwrap_or_else(|| String::from("./private/unavailable_accounts.txt"));
+    let output = options
+        .output
+        .clone()
+        .unwrap_or_else(|| String::from("./data/unavailable_account_hashes.txt"));
+
+    let accounts = load_private_accounts(&input);
+    let mut hashes = load_public_hashes(&output);
+
+    // CAREFUL! Never print or write the plaintext of accounts, only hashes and categories are allowed to be public.
+    match options.command {
+        Command::Update => {
+            let published = hashes.iter().cloned().collect::<HashSet<_>>();
+            let new_accounts = accounts
+                .iter()
+                .filter(|item| !published.contains(&account_to_hash(&item.account)))
+                .collect::<Vec<_>>();
+
+            println!("Added {} hashes of unavailable accounts:", new_accounts.len());
+            for (category, count) in count_by_category(&new_accounts) {
+                println!("{}: {}", category, count);
+            }
+
+            // Append new hashes to the end to keep the diff of the public file minimal.
+            hashes.extend(new_accounts.iter().map(|item| account_to_hash(&item.account)));
+            fs::write(&output, hashes.join("\n") + "\n")
+                .expect(format!("{} should be a writable file.", output).as_str());
+
+            println!("Done ✅");
+        }
+        Command::Verify => {
+            let private = accounts
+                .iter()
+                .map(|item| account_to_hash(&item.account))
+                .collect::<HashSet<_>>();
+            let missing = hashes
+                .iter()
+                .filter(|hash| !private.contains(*hash))
+                .collect::<Vec<_>>();
+            let published = hashes.iter().cloned().collect::<HashSet<_>>();
+            let unpublished = accounts
+                .iter()
+                .filter(|item| !published.contains(&account_to_hash(&item.account)))
+                .collect::<Vec<_>>();
+
+            println!("Published hashes: {}", hashes.len());
+            for (category, count) in count_by_category(&accounts.iter().collect::<Vec<_>>()) {
+                println!("{}: {}", category, count);
+            }
+            println!();
+
+            if !unpublished.is_empty() {
+                println!(
+                    "{} accounts in the private file are not published yet.",
+                    unpublished.len()
+                );
+            }
+
+            if missing.is_empty() {
+                println!("Every published hash has a matching account ✅");
+            } else {
+                println!("Published hashes without matching account:");
+                for hash in missing {
+                    println!("{}", hash);
+                }
+                std::process::exit(1);
+            }
+        }
+    }
+}
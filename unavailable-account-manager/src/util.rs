@@ -0,0 +1,89 @@
+use ckb_hash::blake2b_256;
+use std::{collections::BTreeMap, fs, path::Path};
+
+pub const ACCOUNT_SUFFIX: &str = ".bit";
+
+pub struct UnavailableAccount {
+    pub account: String,
+    pub category: String,
+}
+
+/// Load the plaintext of unavailable accounts, every line is an account and its reason category separated by
+/// whitespaces, the lines start with # are comments.
+pub fn load_private_accounts(input: &str) -> Vec<UnavailableAccount> {
+    let data = fs::read_to_string(input).expect(format!("{} should be a readable file.", input).as_str());
+
+    let mut accounts: Vec<UnavailableAccount> = Vec::new();
+    for (i, line) in data.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        if fields.len() != 2 {
+            panic!(
+                "Line {} of {} should be an account and its reason category.",
+                i + 1,
+                input
+            );
+        }
+
+        let category = fields[1].to_ascii_lowercase();
+        if !category.chars().all(|c| c.is_ascii_lowercase() || c == '_') {
+            panic!("Line {} of {} contains invalid reason category.", i + 1, input);
+        }
+
+        let account = fields[0]
+            .to_ascii_lowercase()
+            .trim_end_matches(ACCOUNT_SUFFIX)
+            .to_string();
+        if accounts.iter().any(|item| item.account == account) {
+            panic!("Line {} of {} contains duplicated account.", i + 1, input);
+        }
+
+        accounts.push(UnavailableAccount { account, category });
+    }
+
+    accounts
+}
+
+/// Load the published hashes, a missing file is treated as empty.
+pub fn load_public_hashes(output: &str) -> Vec<String> {
+    if !Path::new(output).exists() {
+        return Vec::new();
+    }
+
+    fs::read_to_string(output)
+        .expect(format!("{} should be a readable file.", output).as_str())
+        .lines()
+        .map(|line| line.trim().to_ascii_lowercase())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// The hash is calculated from the account without .bit suffix like the reserved accounts in reserved-account-combiner,
+/// the config-cell-type-generator will truncate it to be the account ID.
+pub fn account_to_hash(account: &str) -> String {
+    hex::encode(blake2b_256(account.trim_end_matches(ACCOUNT_SUFFIX).as_bytes()))
+}
+
+pub fn count_by_category(accounts: &[&UnavailableAccount]) -> BTreeMap<String, usize> {
+    let mut ret = BTreeMap::new();
+    for account in accounts {
+        *ret.entry(account.category.clone()).or_default() += 1;
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_to_hash() {
+        let hash = "66d061018784140b38f55d1d5d47c6893fcc063dfd4f3598ab2ab926b7e52ee8";
+        assert_eq!(account_to_hash("das"), hash);
+        assert_eq!(account_to_hash("das.bit"), hash);
+    }
+}